@@ -1,8 +1,10 @@
 use rand::Rng;
 use rand::seq::SliceRandom;
+use serde::Serialize;
 use serde_json::json;
 use std::env;
 use std::fs;
+use std::io::{self, Write};
 use std::os::unix::fs::PermissionsExt;
 use std::process::{Command, Stdio};
 use std::thread;
@@ -27,14 +29,15 @@ fn port_in_use(node: &str, port: u16) -> bool {
     }
 }
 
-// Find a free port on a remote node by randomly selecting ports and checking if they are in use
-fn find_free_port(node: &str, max_attempts: u32) -> Option<u16>
+// Find a free port on a remote node, within `port_range` (inclusive), by
+// randomly selecting ports and checking if they are in use.
+fn find_free_port(node: &str, port_range: (u16, u16), max_attempts: u32) -> Option<u16>
 {
     let mut rng = rand::rng();
     for _ in 0..max_attempts {
-        // Generate a random port
-        let port: u16 =rng.random_range(49152..=65535);
-        
+        // Generate a random port in range
+        let port: u16 = rng.random_range(port_range.0..=port_range.1);
+
         // Check if the port is in use
         if !port_in_use(&node, port) {
             return Some(port); // Return the free port
@@ -46,13 +49,101 @@ fn find_free_port(node: &str, max_attempts: u32) -> Option<u16>
     None // Return None if no free port is found after max_attempts
 }
 
+// Node-side knobs the wizard can provision alongside run-node.sh - kept in
+// sync with webserver::config::RuntimeConfig's field names so the file it
+// writes is something get_config/config::init can already read. `m` is now
+// load-bearing everywhere identifiers are hashed and compared (see
+// config::m()), and an inconsistent value across nodes breaks ring routing,
+// so getting it right here matters as much as the other knobs - it is not
+// staged ahead of anything.
+#[derive(Serialize)]
+struct DeployConfig {
+    m: u32,
+    maintenance_interval_ms: u64,
+}
+
+// Everything the rest of main needs to provision a cluster, whether it came
+// from the old positional argv or the interactive wizard below.
+struct DeployPlan {
+    num_servers: usize,
+    port_range: (u16, u16),
+    node_config: Option<DeployConfig>,
+}
+
+// Prompt on stdin/stdout for node count, port range, target M, and
+// maintenance interval, replacing the brittle `deploy <num_servers>`
+// positional invocation with one that also provisions a consistently
+// configured cluster. Blank input at any prompt keeps that field at its
+// shown default.
+fn run_wizard() -> DeployPlan {
+    println!("Chord cluster deploy wizard (press enter to accept the default)");
+
+    let num_servers = prompt_with_default("Number of nodes", 5usize);
+    let port_start = prompt_with_default("Port range start", 49152u16);
+    let port_end = prompt_with_default("Port range end", 65535u16);
+    let m = prompt_with_default("Identifier width (M, bits)", 16u32);
+    let maintenance_interval_ms = prompt_with_default("Maintenance interval (ms)", 1000u64);
+
+    DeployPlan {
+        num_servers,
+        port_range: (port_start, port_end),
+        node_config: Some(DeployConfig { m, maintenance_interval_ms }),
+    }
+}
+
+fn prompt_with_default<T: std::str::FromStr + std::fmt::Display>(label: &str, default: T) -> T {
+    print!("{} [{}]: ", label, default);
+    io::stdout().flush().expect("failed to flush stdout");
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).expect("failed to read from stdin");
+    let trimmed = line.trim();
+
+    if trimmed.is_empty() {
+        default
+    } else {
+        trimmed.parse().unwrap_or(default)
+    }
+}
+
+// Write the wizard's node config to `node-config.toml` and scp it to every
+// provisioned node alongside run-node.sh, so each node's --config flag (see
+// webserver's main.rs) has something to pick up.
+fn ship_node_config(config: &DeployConfig, nodes: &[String]) {
+    let path = "node-config.toml";
+    let contents = toml::to_string_pretty(config).expect("failed to serialize node config");
+    fs::write(path, contents).expect("failed to write node config");
+
+    for node in nodes {
+        let status = Command::new("scp")
+            .args([path, &format!("{}:~/node-config.toml", node)])
+            .status();
+
+        match status {
+            Ok(status) if status.success() => {}
+            _ => eprintln!("Failed to ship {} to {}", path, node),
+        }
+    }
+}
+
 
 fn main() {
-    // Read number of servers from command line arguments
+    // Read arguments from the command line
     let args: Vec<String> = env::args().collect();
 
-    // Number of servers to deploy
-    let num_servers: usize = args[1].parse().expect("number of servers must be an integer");
+    // `deploy --wizard` walks through node count/port range/M/maintenance
+    // interval interactively; any other invocation keeps the original
+    // `deploy <num_servers>` positional form working unchanged.
+    let plan = if args.get(1).map(String::as_str) == Some("--wizard") {
+        run_wizard()
+    } else {
+        let num_servers: usize = args
+            .get(1)
+            .expect("usage: deploy <num_servers> | deploy --wizard")
+            .parse()
+            .expect("number of servers must be an integer");
+        DeployPlan { num_servers, port_range: (49152, 65535), node_config: None }
+    };
 
     // Download run-node.sh if it doesn't exist
     let run_node_path = "run-node.sh";
@@ -104,13 +195,22 @@ fn main() {
 
     let mut servers = Vec::new();
 
-    for i in 0..num_servers {
-        
+    // Ship the wizard's node config to every node we're about to deploy to,
+    // alongside run-node.sh, before starting any of them.
+    if let Some(node_config) = &plan.node_config {
+        let selected: Vec<String> = (0..plan.num_servers)
+            .map(|i| shuffled_nodes[i % shuffled_nodes.len()].clone())
+            .collect();
+        ship_node_config(node_config, &selected);
+    }
+
+    for i in 0..plan.num_servers {
+
         // Select a node in a round-robin fashion
         let node = &shuffled_nodes[i % shuffled_nodes.len()];
 
         // Find a free port on the selected node
-        let port = match find_free_port(node, 20) {
+        let port = match find_free_port(node, plan.port_range, 20) {
             Some(port) => port,
             None => {
                 eprintln!("Failed to find a free port on node {}", node);
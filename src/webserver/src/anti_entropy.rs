@@ -0,0 +1,139 @@
+// Periodic Merkle-tree anti-entropy pass: compares our Storage against each of
+// our successor-list replicas and repairs any divergence found, bounding the
+// amount of data moved to roughly O(differences * tree depth) instead of
+// re-syncing the whole key space every round.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use crate::chord::{ChordNode, NodeAddr};
+use crate::simulate::CrashState;
+use crate::storage::Storage;
+
+// Anti-entropy is read-heavy but not latency sensitive, so it runs at a
+// slower cadence than stabilize/fix_fingers.
+const INTERVAL_MULTIPLIER: u64 = 5;
+
+#[derive(serde::Deserialize)]
+struct RootView {
+    root: String,
+}
+
+#[derive(serde::Deserialize)]
+struct NodeView {
+    hash: String,
+    #[allow(dead_code)]
+    left: Option<String>,
+    #[allow(dead_code)]
+    right: Option<String>,
+    keys: Option<Vec<String>>,
+}
+
+pub fn spawn(
+    chord: Arc<RwLock<ChordNode>>,
+    storage: Storage,
+    period_ms: u64,
+    crash_state: Arc<CrashState>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(period_ms * INTERVAL_MULTIPLIER));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if crash_state.is_crashed() {
+                continue;
+            }
+
+            let (client, peers) = {
+                let guard = chord.read().await;
+                let me_id = guard.nodes.me.id;
+                let peers = guard
+                    .nodes
+                    .successor_list
+                    .iter()
+                    .filter(|n| n.id != me_id)
+                    .map(|n| n.addr.clone())
+                    .collect::<Vec<_>>();
+                (guard.client.clone(), peers)
+            };
+
+            for peer in peers {
+                let _ = sync_with_peer(&client, &storage, &peer).await;
+            }
+        }
+    });
+}
+
+// Returns None on any network/parse failure; the next round just tries again.
+async fn sync_with_peer(client: &reqwest::Client, storage: &Storage, peer: &NodeAddr) -> Option<()> {
+    let remote_root: RootView = client
+        .get(format!("{}/merkle/root", peer.to_url()))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    if remote_root.root == storage.merkle_root() {
+        return Some(()); // already in sync
+    }
+
+    let leaf_depth = storage.merkle_leaf_depth();
+    let mut stack = vec![String::new()]; // start the descent at the root path
+
+    while let Some(path) = stack.pop() {
+        let local = storage.merkle_node(&path)?;
+        let remote: NodeView = client
+            .get(format!("{}/merkle/node?path={}", peer.to_url(), path))
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+
+        if local.hash == remote.hash {
+            continue; // this whole subtree already matches
+        }
+
+        if path.len() == leaf_depth {
+            reconcile_bucket(client, storage, peer, &local.keys.unwrap_or_default(), &remote.keys.unwrap_or_default()).await;
+        } else {
+            stack.push(format!("{path}0"));
+            stack.push(format!("{path}1"));
+        }
+    }
+
+    Some(())
+}
+
+// A divergent bucket: push every key we hold (the peer merges by version, so
+// pushing a key it's already ahead on is a harmless no-op) and pull anything
+// the peer has, merging locally so neither side can regress a newer value.
+async fn reconcile_bucket(
+    client: &reqwest::Client,
+    storage: &Storage,
+    peer: &NodeAddr,
+    local_keys: &[String],
+    remote_keys: &[String],
+) {
+    for key in local_keys {
+        if let Some(versioned) = storage.get(key) {
+            let _ = client
+                .put(format!("{}/replica/{}", peer.to_url(), key))
+                .json(&versioned)
+                .send()
+                .await;
+        }
+    }
+
+    for key in remote_keys {
+        if let Ok(resp) = client.get(format!("{}/replica/{}", peer.to_url(), key)).send().await {
+            if let Ok(versioned) = resp.json::<crate::storage::VersionedValue>().await {
+                storage.merge(key.clone(), versioned);
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use actix_web::{get, post, put, HttpRequest, HttpResponse, Responder, web};
+use actix_web::{delete, get, post, put, HttpRequest, HttpResponse, Responder, ResponseError, web};
 use std::time::Duration;
 
 use crate::AppState;
-use crate::chord::{Node, NodeAddr};
-use crate::network::{forward_get, forward_put};
-use crate::utils::{in_interval_open_closed, in_interval_open_open};
-use crate::ChordNode;
-use crate::config::HOP_LIMIT;
+use crate::chord::{closest_preceding_on, replicas_for_on, responsible_for_on, KnownNodes, Node, NodeAddr};
+use crate::error::ChordError;
+use crate::network::{forward_batch, forward_delete, forward_get, forward_put, forward_put_stream, BatchOp, BatchResult};
+use futures_util::StreamExt;
+use crate::utils::{hash_key, in_interval_open_closed, in_interval_open_open};
+use crate::config::{self, hop_limit};
+use reqwest::Client;
 
 // Define a handler for the /helloworld route
 #[get("/helloworld")]
@@ -21,7 +23,7 @@ async fn get_storage(
     req: HttpRequest,
     key: web::Path<String>,
     state: web::Data<AppState>,
-) -> impl Responder {
+) -> Result<HttpResponse, ChordError> {
     // get the key from the path and hop count from headers
     let key = key.into_inner();
     let hops = req
@@ -30,30 +32,77 @@ async fn get_storage(
         .and_then(|h| h.to_str().ok().and_then(|s| s.parse::<u32>().ok()))
         .unwrap_or(0);
 
-    // Aquire read lock on chord handler
-    let chord = state.chord.read().await;
+    // Routing only needs a topology snapshot, so read it lock-free instead of
+    // taking the chord RwLock for the whole handler.
+    let nodes = state.topology.borrow().clone();
+    state.request_counters.record_hop_count(hops);
 
-    if chord.responsible_for(&key) {
-        match state.storage.read().await.get(&key) {
-            Some(value) => HttpResponse::Ok().body(value),
+    if responsible_for_on(&nodes, &key) {
+        state.request_counters.record_storage_get(false);
+        Ok(match state.storage.read().await.get(&key) {
+            Some(versioned) if versioned.deleted => HttpResponse::NotFound().body("Key not found"),
+            Some(versioned) => {
+                // A forwarding peer that wants the reply encrypted sets
+                // X-Secure and tells us which peer label to find its session
+                // under (the session it just negotiated with us to send us
+                // this request in the first place).
+                let secure_reply = req.headers().contains_key("X-Secure").then(|| {
+                    req.headers()
+                        .get("X-From-Node")
+                        .and_then(|h| h.to_str().ok())
+                        .and_then(|from| state.secure.session_for(from))
+                }).flatten();
+
+                match secure_reply {
+                    Some(session) => HttpResponse::Ok()
+                        .insert_header(("X-Version", versioned.version.to_string()))
+                        .insert_header(("X-Encrypted", "1"))
+                        .body(crate::secure_transport::encrypt(&session, versioned.value.as_bytes())),
+                    None => HttpResponse::Ok()
+                        .insert_header(("X-Version", versioned.version.to_string()))
+                        .body(versioned.value),
+                }
+            }
             None => HttpResponse::NotFound().body("Key not found"),
-        }
+        })
     } else {
-        match forward_get(&chord, &key, hops).await {
-            Ok(response) => response,
-            Err(_) => HttpResponse::BadGateway().body("Error forwarding request"),
+        state.request_counters.record_storage_get(true);
+        let next_node = closest_preceding_on(&nodes, &state.reliability, hash_key(&key));
+        let storage = state.storage.read().await;
+        match forward_get(&nodes, &state.reliability, &state.client, &state.stream_client, &storage, &key, hops, &state.secure).await {
+            Ok(response) => Ok(response),
+            // If we were forwarding straight to the owner (our own successor)
+            // and it didn't answer, fall back to reading from one of its
+            // replicas instead of surfacing the forwarding error.
+            Err(e) if next_node.id == nodes.successor.id => {
+                Ok(try_replicas(&nodes, &state.client, &key, hops).await.unwrap_or_else(|| e.error_response()))
+            }
+            Err(e) => Err(e),
         }
     }
 }
 
+// Drain an incoming request payload into a single buffer. Used wherever we
+// need the whole body in hand regardless - decrypting an encrypted hop, or
+// validating and persisting a value we're responsible for - as opposed to
+// the plain-forwarding path in put_storage, which streams the payload
+// straight through instead (see forward_put_stream).
+async fn buffer_payload(payload: &mut web::Payload) -> Result<web::Bytes, actix_web::Error> {
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(chunk) = payload.next().await {
+        buf.extend_from_slice(&chunk?);
+    }
+    Ok(web::Bytes::from(buf))
+}
+
 // Takes the key from the path and the value from the request body as UTF-8 string
 #[put("/storage/{key}")]
 async fn put_storage(
     req: HttpRequest,
     key: web::Path<String>,
-    body: web::Bytes,
+    mut payload: web::Payload,
     state: web::Data<AppState>,
-) -> impl Responder {
+) -> Result<HttpResponse, ChordError> {
     // get the key from the path and hop count from headers
     let key = key.into_inner();
     let hops = req
@@ -61,40 +110,186 @@ async fn put_storage(
         .get("X-Chord-Hop-Count")
         .and_then(|h| h.to_str().ok().and_then(|s| s.parse::<u32>().ok()))
         .unwrap_or(0);
+    let encrypted_in = req.headers().contains_key("X-Encrypted");
 
-    // Aquire read lock on chord handler
-    let chord = state.chord.read().await;
+    // Routing only needs a topology snapshot, so read it lock-free instead of
+    // taking the chord RwLock for the whole handler.
+    let nodes = state.topology.borrow().clone();
+    state.request_counters.record_hop_count(hops);
+
+    // A plaintext value that isn't ours to store can be streamed straight
+    // through to the next hop without ever buffering it on this node, as
+    // long as --secure is off. An encrypted hop needs the whole body to
+    // decrypt, a --secure hop needs it to encrypt for the next link, and a
+    // value we're responsible for needs the whole body to validate and
+    // persist, so all of those fall through to the buffered path below.
+    if !encrypted_in && !state.secure.is_enabled() && !responsible_for_on(&nodes, &key) {
+        state.request_counters.record_storage_put(true);
+        return Ok(forward_put_stream(&nodes, &state.reliability, &state.stream_client, &key, payload, hops).await?);
+    }
+
+    let body = buffer_payload(&mut payload)
+        .await
+        .map_err(|_| ChordError::BadPayload("error reading request body".to_string()))?;
 
-    if chord.responsible_for(&key) {
-        let value = match std::str::from_utf8(&body) {
-            Ok(v) => v.to_string(),
-            Err(_) => return HttpResponse::BadRequest().body("Value must be valid UTF-8"),
-        };
-        state.storage.write().await.put(key, value);
-        HttpResponse::Ok().body("Value stored")
+    // A hop that encrypted this request for us tells us which peer label to
+    // find its session under; decrypt up front so everything below this
+    // point (storing locally or forwarding onward) deals with plaintext, and
+    // forward_put re-encrypts independently for whichever hop comes next.
+    let body: web::Bytes = if encrypted_in {
+        let session = req
+            .headers()
+            .get("X-From-Node")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|from| state.secure.session_for(from))
+            .ok_or_else(|| ChordError::BadPayload("no secure session for sender".to_string()))?;
+        let decrypted = crate::secure_transport::decrypt(&session, &body)
+            .ok_or_else(|| ChordError::BadPayload("failed to decrypt value".to_string()))?;
+        web::Bytes::from(decrypted)
     } else {
-        match forward_put(&chord, &key, body, hops).await {
-            Ok(response) => response,
-            Err(_) => HttpResponse::BadGateway().body("Error forwarding request"),
+        body
+    };
+
+    if responsible_for_on(&nodes, &key) {
+        // We're handing our keys off to our successor ahead of a graceful
+        // leave (see post_leave/handoff_all_keys_to) - accepting a fresh
+        // write here now would either get wiped by the handoff's
+        // storage.clear() or resurrect a key after it's already been handed
+        // off, so push the client toward whoever is about to own it instead.
+        if state.draining.is_draining() {
+            return Err(ChordError::Draining);
+        }
+        state.request_counters.record_storage_put(false);
+        let value = std::str::from_utf8(&body)
+            .map_err(|_| ChordError::BadPayload("value must be valid UTF-8".to_string()))?
+            .to_string();
+        let writer = nodes.me.id;
+        let version = state.storage.write().await.put(key.clone(), value.clone(), writer);
+        replicate_write(&state.client, &nodes, key, crate::storage::VersionedValue { value, version, writer, deleted: false });
+        Ok(HttpResponse::Ok()
+            .insert_header(("X-Version", version.to_string()))
+            .body("Value stored"))
+    } else {
+        // Only an encrypted incoming hop or a --secure node forwarding
+        // reaches here - plaintext forwarding with --secure off already
+        // returned above via forward_put_stream.
+        state.request_counters.record_storage_put(true);
+        Ok(forward_put(&nodes, &state.reliability, &state.client, &key, body, hops, &state.secure).await?)
+    }
+}
+
+// Deletes write a tombstone (see storage::Storage::delete) rather than
+// removing the entry, so a concurrent replica push can't resurrect the old
+// value just by arriving after the delete - it loses the (version, writer)
+// comparison in `merge` like any other stale write would.
+#[delete("/storage/{key}")]
+async fn delete_storage(
+    req: HttpRequest,
+    key: web::Path<String>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, ChordError> {
+    let key = key.into_inner();
+    let hops = req
+        .headers()
+        .get("X-Chord-Hop-Count")
+        .and_then(|h| h.to_str().ok().and_then(|s| s.parse::<u32>().ok()))
+        .unwrap_or(0);
+
+    let nodes = state.topology.borrow().clone();
+
+    if responsible_for_on(&nodes, &key) {
+        // Same reasoning as put_storage's draining check: don't let a fresh
+        // delete land on a key that's mid-handoff to our successor.
+        if state.draining.is_draining() {
+            return Err(ChordError::Draining);
+        }
+        let writer = nodes.me.id;
+        let version = state.storage.write().await.delete(key.clone(), writer);
+        replicate_write(&state.client, &nodes, key, crate::storage::VersionedValue { value: String::new(), version, writer, deleted: true });
+        Ok(HttpResponse::Ok()
+            .insert_header(("X-Version", version.to_string()))
+            .body("Value deleted"))
+    } else {
+        Ok(forward_delete(&nodes, &state.reliability, &state.client, &key, hops).await?)
+    }
+}
+
+// Fans a batch of get/put operations out across whichever nodes actually
+// own each key instead of walking the ring one key at a time: ops this node
+// is responsible for are served straight from state.storage, and everything
+// else is grouped by next hop and forwarded in one concurrent round of
+// sub-requests (see forward_batch). Results come back in request order,
+// each with its own status, so one crashed node failing its sub-request
+// doesn't fail the whole batch.
+#[post("/storage-batch")]
+async fn storage_batch(
+    req: HttpRequest,
+    ops: web::Json<Vec<BatchOp>>,
+    state: web::Data<AppState>,
+) -> impl Responder {
+    let hops = req
+        .headers()
+        .get("X-Chord-Hop-Count")
+        .and_then(|h| h.to_str().ok().and_then(|s| s.parse::<u32>().ok()))
+        .unwrap_or(0);
+
+    let nodes = state.topology.borrow().clone();
+
+    let mut local: Vec<(usize, BatchResult)> = Vec::new();
+    let mut remote: Vec<(usize, BatchOp)> = Vec::new();
+    for (i, op) in ops.into_inner().into_iter().enumerate() {
+        if responsible_for_on(&nodes, op.key()) {
+            local.push((i, handle_local_batch_op(&state, &nodes, op).await));
+        } else {
+            remote.push((i, op));
+        }
+    }
+
+    let mut results = local;
+    results.extend(forward_batch(&nodes, &state.reliability, &state.client, remote, hops).await);
+    results.sort_by_key(|(i, _)| *i);
+
+    HttpResponse::Ok().json(results.into_iter().map(|(_, result)| result).collect::<Vec<_>>())
+}
+
+// Serves one batch operation this node is directly responsible for, without
+// any HTTP round trip - mirrors get_storage/put_storage's local branches.
+async fn handle_local_batch_op(state: &web::Data<AppState>, nodes: &KnownNodes, op: BatchOp) -> BatchResult {
+    match op {
+        BatchOp::Get { key } => match state.storage.read().await.get(&key) {
+            Some(versioned) if !versioned.deleted => BatchResult {
+                key,
+                status: 200,
+                value: Some(versioned.value),
+                version: Some(versioned.version),
+                error: None,
+            },
+            _ => BatchResult { key, status: 404, value: None, version: None, error: Some("Key not found".to_string()) },
+        },
+        BatchOp::Put { key, value } => {
+            // Same draining check as put_storage's local branch.
+            if state.draining.is_draining() {
+                return BatchResult { key, status: 503, value: None, version: None, error: Some(ChordError::Draining.to_string()) };
+            }
+            let writer = nodes.me.id;
+            let version = state.storage.write().await.put(key.clone(), value.clone(), writer);
+            replicate_write(&state.client, nodes, key.clone(), crate::storage::VersionedValue { value, version, writer, deleted: false });
+            BatchResult { key, status: 200, value: None, version: Some(version), error: None }
         }
     }
 }
 
 #[get("/node-info")]
 async fn get_node_info(state: web::Data<AppState>) -> impl Responder {
-    // Aquire read lock on chord handler
-    let chord = state.chord.read().await;
-    // Get current node info
-    let node_info = chord.nodes.to_viewmodel();
+    // Viewmodel generation is read-only, so take it from the lock-free
+    // topology snapshot rather than the chord RwLock.
+    let node_info = state.topology.borrow().to_viewmodel();
     HttpResponse::Ok().json(node_info)
 }
 
 #[get("/known-nodes")]
 async fn get_known_nodes(state: web::Data<AppState>) -> impl Responder {
-    // Aquire read lock on chord handler
-    let chord = state.chord.read().await;
-    // Get known nodes info
-    let known_nodes = chord.nodes.get_all_nodes();
+    let known_nodes = state.topology.borrow().get_all_nodes();
     HttpResponse::Ok().json(known_nodes)
 }
 
@@ -102,69 +297,102 @@ async fn get_known_nodes(state: web::Data<AppState>) -> impl Responder {
 async fn post_join(
     query: web::Query<std::collections::HashMap<String, String>>,
     state: web::Data<AppState>,
-) -> impl Responder {
+) -> Result<HttpResponse, ChordError> {
     // Get nprime parameter from query string
-    if let Some(nprime) = query.get("nprime") {
-        // Create a NodeAddr from the nprime string
-        let parts: Vec<&str> = nprime.split(':').collect();
-        if parts.len() == 2 {
-            let host = parts[0].to_string();
-            if let Ok(port) = parts[1].parse::<u16>() {
-                let addr = NodeAddr { host, port };
-                
-                // Prepare join and do RPCs without holding write lock
-                let join_result = {
-                    let chord = state.chord.read().await;
-                    chord.join_prepare(addr).await
-                };
-                
-                // Apply state changes, with write lock
-                match join_result {
-                    Ok(Some((successor, finger_updates))) => {
-                        let mut chord = state.chord.write().await;
-                        chord.join_apply(successor, finger_updates);
-                        HttpResponse::Ok().body("Joined the DHT successfully")
-                    },
-                    Ok(None) => HttpResponse::Ok().body("Already in network"),
-                    Err(e) => HttpResponse::BadGateway().body(format!("Error joining DHT: {}", e)),
-                }
-            } else {
-                HttpResponse::BadRequest().body("Invalid port number")
-            }
-        } else {
-            HttpResponse::BadRequest().body("Invalid nprime format")
-        }
-    } else {
-        HttpResponse::BadRequest().body("Missing nprime parameter")
+    let Some(nprime) = query.get("nprime") else {
+        return Err(ChordError::InvalidNodeAddr("missing nprime parameter".to_string()));
+    };
+    // Create a NodeAddr from the nprime string
+    let parts: Vec<&str> = nprime.split(':').collect();
+    if parts.len() != 2 {
+        return Err(ChordError::InvalidNodeAddr("invalid nprime format".to_string()));
+    }
+    let host = parts[0].to_string();
+    let Ok(port) = parts[1].parse::<u16>() else {
+        return Err(ChordError::InvalidNodeAddr("invalid port number".to_string()));
+    };
+    let addr = NodeAddr { host, port };
+
+    // Prepare join and do RPCs without holding write lock
+    let join_result = {
+        let chord = state.chord.read().await;
+        chord.join_prepare(addr).await
+    };
+
+    // Apply state changes, with write lock
+    match join_result {
+        Ok(Some((successor, finger_updates, successor_list))) => {
+            let mut chord = state.chord.write().await;
+            chord.join_apply(successor, finger_updates, successor_list);
+            Ok(HttpResponse::Ok().body("Joined the DHT successfully"))
+        },
+        Ok(None) => Ok(HttpResponse::Ok().body("Already in network")),
+        Err(e) => Err(ChordError::Internal(format!("error joining DHT: {}", e))),
     }
 }
 
 #[post("/leave")]
-async fn post_leave(state: web::Data<AppState>) -> impl Responder {
+async fn post_leave(state: web::Data<AppState>) -> Result<HttpResponse, ChordError> {
+    match graceful_leave(&state).await? {
+        true => Ok(HttpResponse::Ok().body("Left the DHT successfully")),
+        false => Ok(HttpResponse::Ok().body("Already a single node")),
+    }
+}
+
+// Relink our predecessor/successor around us and hand off our keys to our
+// successor, the same sequence POST /leave performs - factored out so
+// shutdown::graceful_leave can drive the identical departure on SIGINT/
+// SIGTERM/idle-timeout instead of a node only ever leaving cleanly when
+// something remembers to call /leave first. Returns Ok(true) if a departure
+// actually happened, Ok(false) if this was already a single-node ring.
+pub(crate) async fn graceful_leave(state: &web::Data<AppState>) -> Result<bool, ChordError> {
     // Prepare leave and do RPCs without holding write lock
     let should_leave = {
         let chord = state.chord.read().await;
         chord.leave_prepare().await
     };
-    
+
     // Apply leave if needed, with write lock
     match should_leave {
         Ok(true) => {
+            // Gate fresh local writes while we hand our keys off - see
+            // DrainState and put_storage/delete_storage's draining checks.
+            state.draining.begin();
+
+            let successor = {
+                let chord = state.chord.read().await;
+                chord.nodes.successor.clone()
+            };
+
+            if !handoff_all_keys_to(state, &successor).await {
+                // Handoff didn't succeed - bail out rather than finalize the
+                // leave and lose every key we hold. Our own ring state and
+                // keys stay as they were, but leave_prepare has already told
+                // our predecessor and successor to link to each other, so
+                // they point past this node until stabilize/notify relink it.
+                state.draining.finish();
+                return Err(ChordError::Internal("key handoff to successor failed".to_string()));
+            }
+
             let mut chord = state.chord.write().await;
             chord.leave_apply();
-            HttpResponse::Ok().body("Left the DHT successfully")
+            drop(chord);
+
+            state.draining.finish();
+            Ok(true)
         },
-        Ok(false) => HttpResponse::Ok().body("Already a single node"),
-        Err(e) => HttpResponse::BadGateway().body(format!("Error leaving DHT: {}", e)),
+        Ok(false) => Ok(false),
+        Err(e) => Err(ChordError::Internal(format!("error leaving DHT: {}", e))),
     }
 }
 
 #[post("/reset")]
 async fn post_reset(state: web::Data<AppState>) -> impl Responder {
     let mut chord = state.chord.write().await;
-    // Create a completely new ChordNode with the same address
-    let addr = chord.nodes.me.addr.clone();
-    *chord = ChordNode::new(addr);
+    // Reset in place (rather than replacing with a fresh ChordNode) so the
+    // existing `topology` watch channel - and the receiver AppState handed
+    // out at startup - keeps working after a reset.
+    chord.reset();
     drop(chord); // Release lock before clearing storage
     
     // Also clear storage
@@ -184,6 +412,250 @@ async fn post_sim_recover(state: web::Data<AppState>) -> impl Responder {
     HttpResponse::Ok().body("Node recovered - responses enabled")
 }
 
+// Transfer every locally stored key that now falls in (old_predecessor_id, new_predecessor.id]
+// to the new predecessor, then drop them from local storage. Best-effort: if the new
+// predecessor can't be reached the keys stay put and a later anti-entropy pass can retry.
+async fn handoff_keys_to(state: &web::Data<AppState>, old_predecessor_id: u64, new_predecessor: &Node) {
+    let moving: Vec<(String, crate::storage::VersionedValue)> = {
+        let storage = state.storage.read().await;
+        storage
+            .entries()
+            .into_iter()
+            .filter(|(k, _)| in_interval_open_closed(hash_key(k), old_predecessor_id, new_predecessor.id, config::m()))
+            .collect()
+    };
+
+    if moving.is_empty() {
+        return;
+    }
+
+    let url = format!("{}/handoff", new_predecessor.addr.to_url());
+    if state.client.post(&url).json(&moving).send().await.is_ok() {
+        let storage = state.storage.read().await;
+        for (key, _) in &moving {
+            storage.remove(key);
+        }
+    }
+}
+
+// Push every key this node holds to `successor` ahead of finalizing a
+// graceful leave (see post_leave). Unlike handoff_keys_to's partial-range
+// push on a predecessor change, a leaving node's whole range is inherited by
+// its successor once pred <-> succ are relinked, so there's no interval
+// filter here - everything moves. Storage is only cleared once the push is
+// acknowledged, so a handoff that fails (successor unreachable, say) leaves
+// the data right where it is instead of losing it. Returns whether it's now
+// safe to finalize the leave.
+async fn handoff_all_keys_to(state: &web::Data<AppState>, successor: &Node) -> bool {
+    let all: Vec<(String, crate::storage::VersionedValue)> = state.storage.read().await.entries();
+    if all.is_empty() {
+        return true;
+    }
+
+    let url = format!("{}/handoff", successor.addr.to_url());
+    match state.client.post(&url).json(&all).send().await {
+        Ok(resp) if resp.status().is_success() => {
+            state.storage.read().await.clear();
+            true
+        }
+        _ => false,
+    }
+}
+
+// Push a just-written key (put or tombstone) to every node in our successor
+// list (via `replicas_for_on`) so replicas stay in sync, without re-entering the
+// Chord routing/responsibility logic. The full VersionedValue, including its
+// version/writer, rides along so receivers merge instead of overwrite.
+fn replicate_write(client: &Client, nodes: &KnownNodes, key: String, versioned: crate::storage::VersionedValue) {
+    let client = client.clone();
+    let targets: Vec<NodeAddr> = replicas_for_on(nodes).into_iter().map(|n| n.addr).collect();
+
+    tokio::spawn(async move {
+        for addr in targets {
+            let url = format!("{}/replica/{}", addr.to_url(), key);
+            let _ = client.put(url).json(&versioned).send().await;
+        }
+    });
+}
+
+// Read a key directly from local Storage, bypassing Chord routing. Used to
+// serve a replica read when the owner is down and for anti-entropy pulls;
+// replicated writes arrive through put_replica below.
+#[get("/replica/{key}")]
+async fn get_replica(key: web::Path<String>, state: web::Data<AppState>) -> impl Responder {
+    match state.storage.read().await.get(&key.into_inner()) {
+        Some(versioned) if versioned.deleted => HttpResponse::NotFound().body("Key not found"),
+        Some(versioned) => HttpResponse::Ok().json(versioned),
+        None => HttpResponse::NotFound().body("Key not found"),
+    }
+}
+
+#[put("/replica/{key}")]
+async fn put_replica(
+    key: web::Path<String>,
+    body: web::Json<crate::storage::VersionedValue>,
+    state: web::Data<AppState>,
+) -> impl Responder {
+    state.storage.write().await.merge(key.into_inner(), body.into_inner());
+    HttpResponse::Ok().body("Replica stored")
+}
+
+// If the owner we routed a GET to is unreachable, fall back to our own
+// successor list, which mirrors the owner's own list one hop behind. Shares
+// the caller's hop count with the same hop_limit() check forward_get/forward_put
+// use, so a request that already chained most of the way around a ring with
+// a fully-crashed replica set still terminates with a clean error instead of
+// this fallback adding its own unbounded walk on top.
+async fn try_replicas(nodes: &KnownNodes, client: &Client, key: &str, hop_count: u32) -> Option<HttpResponse> {
+    if hop_count >= hop_limit() {
+        return None;
+    }
+
+    let me_id = nodes.me.id;
+    let dead_owner_id = nodes.successor.id;
+    for node in nodes.successor_list.iter().skip(1) {
+        if node.id == me_id || node.id == dead_owner_id {
+            continue;
+        }
+        let url = format!("{}/replica/{}", node.addr.to_url(), key);
+        if let Ok(resp) = client.get(&url).send().await {
+            match resp.status() {
+                actix_web::http::StatusCode::OK => {
+                    if let Ok(versioned) = resp.json::<crate::storage::VersionedValue>().await {
+                        // get_replica answers 404 for a tombstone rather than
+                        // serving it; a deleted value that arrives anyway is
+                        // treated the same as a 404 instead of resurrecting it.
+                        if versioned.deleted {
+                            return Some(HttpResponse::NotFound().body("Key not found"));
+                        }
+                        return Some(
+                            HttpResponse::Ok()
+                                .insert_header(("X-Version", versioned.version.to_string()))
+                                .body(versioned.value),
+                        );
+                    }
+                }
+                actix_web::http::StatusCode::NOT_FOUND => {
+                    return Some(HttpResponse::NotFound().body("Key not found"));
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+#[derive(serde::Serialize)]
+struct MerkleRootView {
+    root: String,
+}
+
+// Root hash of our Merkle summary, for a cheap "are we in sync" check
+#[get("/merkle/root")]
+async fn merkle_root(state: web::Data<AppState>) -> impl Responder {
+    let root = state.storage.read().await.merkle_root();
+    HttpResponse::Ok().json(MerkleRootView { root })
+}
+
+// Resolve a node in the Merkle tree by its root-to-node path (a string of
+// '0'/'1' characters, empty = root), for anti-entropy descent
+#[get("/merkle/node")]
+async fn merkle_node(
+    query: web::Query<std::collections::HashMap<String, String>>,
+    state: web::Data<AppState>,
+) -> impl Responder {
+    let path = query.get("path").cloned().unwrap_or_default();
+    match state.storage.read().await.merkle_node(&path) {
+        Some(view) => HttpResponse::Ok().json(view),
+        None => HttpResponse::BadRequest().body("Invalid merkle path"),
+    }
+}
+
+// Debug view of per-peer reliability (RTT EWMA, failure streak, alive/dead)
+// as tracked by routing; not consulted by any protocol logic itself
+#[get("/node-health")]
+async fn node_health(state: web::Data<AppState>) -> impl Responder {
+    let snapshot = state.chord.read().await.reliability.snapshot();
+    HttpResponse::Ok().json(snapshot)
+}
+
+// RPC/routing/maintenance counters and histograms (see metrics::Metrics),
+// plus request-level counters and ring/storage gauges (see
+// metrics::RequestCounters), all in Prometheus text format. Scraped
+// periodically, so a quick read lock for the chord-side half is fine here.
+#[get("/metrics")]
+async fn metrics(state: web::Data<AppState>) -> impl Responder {
+    let nodes = state.topology.borrow().clone();
+    // Distinct nodes across every routing field, self included.
+    let mut known_ids: std::collections::HashSet<u64> = nodes.finger_table.iter().skip(1).map(|f| f.node.id).collect();
+    known_ids.extend(nodes.successor_list.iter().map(|n| n.id));
+    known_ids.extend([nodes.me.id, nodes.successor.id, nodes.predecessor.id]);
+    let known_nodes = known_ids.len() as u64;
+    let storage_entries = state.storage.read().await.len() as u64;
+    let crashed = state.crash_state.is_crashed();
+
+    let mut body = state.chord.read().await.metrics.render();
+    body.push_str(&state.request_counters.render(storage_entries, known_nodes, crashed));
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(body)
+}
+
+#[derive(serde::Serialize)]
+struct PubkeyView {
+    pubkey: Vec<u8>,
+}
+
+// This node's static secure-transport public key (compressed secp256k1), used
+// by a peer as the first step of the ECIES handshake. Returned regardless of
+// --secure so a peer can tell a node apart from one that never generated a
+// keypair; the handshake itself is refused unless both sides have --secure on.
+#[get("/internal/pubkey")]
+async fn get_pubkey(state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(PubkeyView { pubkey: state.secure.identity().public.serialize().to_vec() })
+}
+
+#[derive(serde::Deserialize)]
+struct HandshakeRequest {
+    from: String,
+    ephemeral_pubkey: Vec<u8>,
+}
+
+// Responder side of the ECIES handshake: derive the session from the
+// initiator's ephemeral public key and our own static secret, and cache it
+// under the initiator's label for decrypting its future traffic (and
+// encrypting our replies to it - the session is reused bidirectionally).
+#[post("/internal/handshake")]
+async fn handshake(state: web::Data<AppState>, body: web::Json<HandshakeRequest>) -> impl Responder {
+    if !state.secure.is_enabled() {
+        return HttpResponse::ServiceUnavailable().body("Secure transport not enabled on this node");
+    }
+    let req = body.into_inner();
+    let ephemeral_pubkey = match secp256k1::PublicKey::from_slice(&req.ephemeral_pubkey) {
+        Ok(key) => key,
+        Err(_) => return HttpResponse::BadRequest().body("Invalid ephemeral public key"),
+    };
+    let session = crate::secure_transport::respond(&ephemeral_pubkey, &state.secure.identity().secret);
+    state.secure.install_session(&req.from, session);
+    HttpResponse::Ok().finish()
+}
+
+// Bulk-accept keys handed off by a node that just learned of a new predecessor.
+// Merged rather than blindly overwritten so a handoff can never resurrect a
+// value older than one we already hold for the same key.
+#[post("/handoff")]
+async fn handoff(
+    state: web::Data<AppState>,
+    body: web::Json<Vec<(String, crate::storage::VersionedValue)>>,
+) -> impl Responder {
+    let storage = state.storage.write().await;
+    for (key, versioned) in body.into_inner() {
+        storage.merge(key, versioned);
+    }
+    HttpResponse::Ok().finish()
+}
+
 // --- Internal RPC endpoints ...
 
 // Ping another node to check if it's alive
@@ -207,6 +679,13 @@ async fn get_predecessor(state: web::Data<AppState>) -> impl Responder {
     HttpResponse::Ok().json(chord.nodes.predecessor.clone())
 }
 
+// Get the current node's successor list (the nodes that replicate our keys)
+#[get("/internal/successor-list")]
+async fn get_successor_list(state: web::Data<AppState>) -> impl Responder {
+    let chord = state.chord.read().await;
+    HttpResponse::Ok().json(chord.nodes.successor_list.clone())
+}
+
 // Find the successor for a given ID
 // n.find_successor(id)
 //  if id ∈ (n, successor]
@@ -231,7 +710,7 @@ async fn find_successor(
         .unwrap_or(0);
     
     // If we've exceeded hop limit, return successor to break the chain
-    if hops >= HOP_LIMIT {
+    if hops >= hop_limit() {
         let chord = state.chord.read().await;
         return HttpResponse::Ok().json(chord.nodes.successor.clone());
     }
@@ -242,8 +721,10 @@ async fn find_successor(
         let me = chord.nodes.me.clone();
         let successor = chord.nodes.successor.clone();
         
-        // Check if id is in (n, successor]
-        if in_interval_open_closed(id, me.id, successor.id) {
+        // Check if id is in (n, successor] - this is where the lookup actually
+        // resolves, so `hops` here is the full depth the lookup took
+        if in_interval_open_closed(id, me.id, successor.id, config::m()) {
+            chord.metrics.record_lookup_hops(hops);
             return HttpResponse::Ok().json(successor);
         }
         
@@ -305,6 +786,7 @@ async fn notify(
             let me = chord_write.nodes.me.clone();
             let predecessor = chord_write.nodes.predecessor.clone();
             let successor = chord_write.nodes.successor.clone();
+            let mut new_predecessor: Option<Node> = None;
 
             // If we're alone (successor is self), the notifying node becomes our successor too
             if successor.id == me.id {
@@ -312,13 +794,31 @@ async fn notify(
                 chord_write.nodes.predecessor = n0.clone();
                 // Update first finger table entry
                 if chord_write.nodes.finger_table.len() > 1 {
-                    chord_write.nodes.finger_table[1].node = n0;
+                    chord_write.nodes.finger_table[1].node = n0.clone();
                 }
-            } 
+                new_predecessor = Some(n0);
+            }
             // Otherwise check if predecessor should be updated
-            else if predecessor.id == me.id || in_interval_open_open(n0.id, predecessor.id, me.id) {
-                chord_write.nodes.predecessor = n0;
+            else if predecessor.id == me.id || in_interval_open_open(n0.id, predecessor.id, me.id, config::m()) {
+                chord_write.nodes.predecessor = n0.clone();
+                new_predecessor = Some(n0);
             }
+            if new_predecessor.is_some() {
+                chord_write.metrics.record_predecessor_change();
+            }
+            chord_write.publish_topology();
+            drop(chord_write);
+
+            // A new predecessor means some of the keys we hold now belong to it;
+            // hand them off in the background rather than blocking the notify reply
+            if let Some(n0) = new_predecessor {
+                let old_predecessor_id = predecessor.id;
+                let state = state.clone();
+                tokio::spawn(async move {
+                    handoff_keys_to(&state, old_predecessor_id, &n0).await;
+                });
+            }
+
             HttpResponse::Ok().finish()
         },
         Err(_) => {
@@ -334,7 +834,7 @@ async fn notify(
 async fn set_successor(
     state: web::Data<AppState>,
     body: web::Json<Node>,
-) -> impl Responder {
+) -> Result<HttpResponse, ChordError> {
     // Use timeout to prevent deadlock
     match tokio::time::timeout(
         Duration::from_millis(200),
@@ -342,11 +842,11 @@ async fn set_successor(
     ).await {
         Ok(mut chord_write) => {
             chord_write.nodes.successor = body.into_inner();
-            HttpResponse::Ok().finish()
+            chord_write.metrics.record_successor_change();
+            chord_write.publish_topology();
+            Ok(HttpResponse::Ok().finish())
         },
-        Err(_) => {
-            HttpResponse::RequestTimeout().body("Timeout acquiring lock")
-        }
+        Err(_) => Err(ChordError::LockTimeout),
     }
 }
 
@@ -356,7 +856,7 @@ async fn set_successor(
 async fn set_predecessor(
     state: web::Data<AppState>,
     body: web::Json<Node>,
-) -> impl Responder {
+) -> Result<HttpResponse, ChordError> {
     // Use timeout to prevent deadlock
     match tokio::time::timeout(
         Duration::from_millis(200),
@@ -364,10 +864,10 @@ async fn set_predecessor(
     ).await {
         Ok(mut chord_write) => {
             chord_write.nodes.predecessor = body.into_inner();
-            HttpResponse::Ok().finish()
+            chord_write.metrics.record_predecessor_change();
+            chord_write.publish_topology();
+            Ok(HttpResponse::Ok().finish())
         },
-        Err(_) => {
-            HttpResponse::RequestTimeout().body("Timeout acquiring lock")
-        }
+        Err(_) => Err(ChordError::LockTimeout),
     }
 }
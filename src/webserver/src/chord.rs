@@ -4,8 +4,16 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use crate::utils::{hash_key, in_interval_open_closed, in_interval_open_open};
-use crate::config::M;
+use crate::config::{self, CONNECTION_POOL_IDLE_TIMEOUT_SECS, CONNECTION_POOL_MAX_IDLE_PER_HOST, SUCCESSOR_LIST_SIZE};
+use crate::metrics::{timed_rpc, Metrics, RpcKind};
+use crate::reliability::ReliabilityTracker;
 use crate::simulate::CrashState;
+use crate::tls;
+use crate::worker::{spawn_all, Worker};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::watch;
 
 // Define a custom result type for Chord operations
 type ChordResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
@@ -17,8 +25,18 @@ pub struct NodeAddr {
 }
 
 impl NodeAddr {
+    // Every peer, including ourselves, is addressed over TCP (http/https)
+    // here - there is deliberately no Unix-domain-socket form. A `--uds`
+    // listen mode was tried (and reverted - see main.rs's git history) so
+    // many simulated nodes could share one machine without burning a TCP
+    // port each, but making it reachable would mean this function picking a
+    // `unix://` scheme and the reqwest clients in ChordNode::new growing a
+    // custom UDS-aware connector, which is a bigger change than a listen
+    // mode toggle. Closed as infeasible at this scope rather than landing an
+    // unreachable node again; revisit only alongside that connector work.
     pub fn to_url(&self) -> String {
-        format!("http://{}:{}", self.host, self.port)
+        let scheme = if config::tls_enabled() { "https" } else { "http" };
+        format!("{}://{}:{}", scheme, self.host, self.port)
     }
 
     pub fn label(&self) -> String {
@@ -51,6 +69,10 @@ pub struct KnownNodes {
     pub predecessor: Node,
     pub successor: Node,
     pub finger_table: Vec<FingerEntry>,
+    // The next SUCCESSOR_LIST_SIZE nodes after `successor` on the ring (successor
+    // itself included at index 0), kept fresh by stabilize so that a crashed
+    // successor can be routed around and its replicated keys read back.
+    pub successor_list: Vec<Node>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -106,85 +128,209 @@ impl KnownNodes {
 pub struct ChordNode {
     pub nodes: KnownNodes,
     pub client: Client,
+    // A second client used only for streamed storage forwarding (see
+    // network::forward_get/forward_put_stream). It sets a connect_timeout
+    // but no whole-request timeout, so a slow-but-alive node sending a large
+    // value isn't killed mid-transfer the way `client`'s per-request
+    // deadlines would kill it.
+    pub stream_client: Client,
+    // Per-peer RTT/failure tracking, consulted by closest_preceding_node so
+    // routing prefers live, fast finger candidates over flaky ones.
+    pub reliability: Arc<ReliabilityTracker>,
+    // RPC/routing/maintenance counters, shared across clones like `reliability`;
+    // exposed at GET /metrics in Prometheus text format (see metrics.rs).
+    pub metrics: Arc<Metrics>,
     fix_next: AtomicUsize, // Stores the current next finger index to fix in [1, M]
+    // Lock-free broadcast of `nodes`, published by every mutation (see
+    // publish_topology). Readers that only need routing-level topology -
+    // closest_preceding_node, responsible_for, viewmodel generation - can
+    // subscribe() once and read it forever without ever taking the
+    // surrounding RwLock<ChordNode>, so they never stall behind a writer.
+    topology: watch::Sender<KnownNodes>,
 }
 
 // Manual clone implementation for ChordNode
 impl Clone for ChordNode {
     fn clone(&self) -> Self {
+        let (topology, _) = watch::channel(self.nodes.clone());
         Self {
             nodes: self.nodes.clone(),
             client: self.client.clone(),
+            stream_client: self.stream_client.clone(),
+            reliability: Arc::clone(&self.reliability),
+            metrics: Arc::clone(&self.metrics),
             fix_next: AtomicUsize::new(self.fix_next.load(Ordering::Relaxed)),
+            topology,
         }
     }
 }
 
+// Routing logic factored out as free functions over a `KnownNodes` snapshot
+// (plus the reliability tracker, which is independently thread-safe) rather
+// than `&ChordNode`, so callers holding only a lock-free `watch::Receiver`
+// snapshot - not the surrounding RwLock<ChordNode> - can still route. The
+// `ChordNode` methods of the same name just delegate to these.
+
+pub fn responsible_for_on(nodes: &KnownNodes, key: &str) -> bool {
+    in_interval_open_closed(hash_key(key), nodes.predecessor.id, nodes.me.id, config::m())
+}
+
+pub fn closest_preceding_on(nodes: &KnownNodes, reliability: &ReliabilityTracker, id: u64) -> Node {
+    // Collect every finger in the closest-preceding interval, furthest-first
+    // (the classic Chord order), then the successor list (closest-following,
+    // so appended last) so a dead or stale finger table never dead-ends
+    // forwarding - the successor list is refreshed on every stabilize and is
+    // much more likely to still be reachable. Then prefer whichever candidate
+    // the reliability tracker rates as live and lowest-latency instead of
+    // blindly taking the first.
+    let candidates: Vec<&Node> = nodes
+        .finger_table
+        .iter()
+        .skip(1)
+        .rev()
+        .filter(|finger| in_interval_open_open(finger.node.id, nodes.me.id, id, config::m()))
+        .map(|finger| &finger.node)
+        .chain(
+            nodes
+                .successor_list
+                .iter()
+                .filter(|n| n.id != nodes.me.id)
+                .filter(|n| in_interval_open_open(n.id, nodes.me.id, id, config::m())),
+        )
+        .collect();
+
+    let best = candidates
+        .iter()
+        .filter(|n| !reliability.is_dead(&n.addr.label()))
+        .min_by(|a, b| {
+            reliability
+                .rtt_ewma_ms(&a.addr.label())
+                .partial_cmp(&reliability.rtt_ewma_ms(&b.addr.label()))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .copied();
+
+    // Fall back to the classic furthest-first pick if every candidate looks
+    // dead (better to try a possibly-recovered node than stall routing),
+    // then to successor if the finger table had no match at all.
+    best.or_else(|| candidates.first().copied())
+        .cloned()
+        .unwrap_or_else(|| nodes.successor.clone())
+}
+
+// Every node that replicates a key owned by `nodes.me`, besides itself: see
+// ChordNode::replicas_for.
+pub fn replicas_for_on(nodes: &KnownNodes) -> Vec<Node> {
+    nodes.successor_list.iter().filter(|n| n.id != nodes.me.id).cloned().collect()
+}
+
 // Implement routing and ChordNode operations
 impl ChordNode {
     // Init single node network on startup
     pub fn new (addr: NodeAddr) -> Self {
         // Create a Node for ourselves
         let node = Node::new(addr);
+        let m = config::m();
         // Set predecessor and successor to ourselves
         let mut known_nodes = KnownNodes {
             me: node.clone(),
             predecessor: node.clone(),
             successor: node.clone(),
-            finger_table: Vec::with_capacity(M as usize + 1),
+            finger_table: Vec::with_capacity(m as usize + 1),
+            successor_list: vec![node.clone(); SUCCESSOR_LIST_SIZE],
         };
         // Push first finger entry - index 0 (not used)
         known_nodes.finger_table.push(FingerEntry { start: node.id, node: node.clone() });
-        
+
         // Fill finger table with self references
         // finger[i] should point to successor of (n + 2^(i-1)) mod 2^M
-        let id_space_mask = if M == 64 { u64::MAX } else { (1u64 << M) - 1 };
-        for i in 1..=M {
+        let id_space_mask = if m == 64 { u64::MAX } else { (1u64 << m) - 1 };
+        for i in 1..=m {
             let offset = 1u64 << ((i - 1) as u32);
             let start = (node.id.wrapping_add(offset)) & id_space_mask;
             known_nodes.finger_table.push(FingerEntry { start, node: node.clone() });
         }
 
-        // Create HTTP client optimized for cluster network
-        let client = Client::builder()
-            .timeout(Duration::from_secs(3))  // Reduced from 5s for cluster network
-            .connect_timeout(Duration::from_millis(500))  // Fast connection for cluster
-            .pool_idle_timeout(Duration::from_secs(30))  // Keep connections longer
-            .pool_max_idle_per_host(10)  // More connections per host for concurrent requests
-            .build()
-            .unwrap_or_else(|_| Client::default());
+        // Create HTTP client optimized for cluster network. tls::configure_client
+        // trusts the cluster CA (or, in --tls-dev, skips verification entirely)
+        // so this client can reach peers over HTTPS once TLS is configured - see
+        // NodeAddr::to_url, which is what decides whether peers are even
+        // addressed as https:// in the first place.
+        let client = tls::configure_client(
+            Client::builder()
+                .timeout(Duration::from_secs(3))  // Reduced from 5s for cluster network
+                .connect_timeout(Duration::from_millis(500))  // Fast connection for cluster
+                .pool_idle_timeout(Duration::from_secs(CONNECTION_POOL_IDLE_TIMEOUT_SECS))  // Keep connections longer
+                .pool_max_idle_per_host(CONNECTION_POOL_MAX_IDLE_PER_HOST),  // More connections per host for concurrent requests
+        )
+        .build()
+        .unwrap_or_else(|_| Client::default());
+
+        // No overall `.timeout()` here on purpose: storage forwarding streams
+        // the body through rather than buffering it (see network.rs), so the
+        // only thing worth bounding is how long connecting to a dead node
+        // takes, not how long a large-but-live transfer runs.
+        let stream_client = tls::configure_client(
+            Client::builder()
+                .connect_timeout(Duration::from_millis(1000))
+                .pool_idle_timeout(Duration::from_secs(CONNECTION_POOL_IDLE_TIMEOUT_SECS))
+                .pool_max_idle_per_host(CONNECTION_POOL_MAX_IDLE_PER_HOST),
+        )
+        .build()
+        .unwrap_or_else(|_| Client::default());
+
+        let (topology, _) = watch::channel(known_nodes.clone());
 
         // Return the ChordNode
         ChordNode {
             nodes: known_nodes,
             client,
+            stream_client,
+            reliability: Arc::new(ReliabilityTracker::new()),
+            metrics: Arc::new(Metrics::new()),
             fix_next: AtomicUsize::new(1), // Start at 1 since finger table is 1-indexed now
+            topology,
         }
     }
 
+    // A cheap, lock-free snapshot receiver of this node's topology. Cloning
+    // the receiver and reading it with `.borrow()` never blocks a writer -
+    // unlike `node.read().await` on the surrounding RwLock<ChordNode>, which
+    // can stall behind a write lock held by stabilize/fix_fingers. Callers
+    // that need to react to topology changes can `.changed().await` on it.
+    pub fn subscribe(&self) -> watch::Receiver<KnownNodes> {
+        self.topology.subscribe()
+    }
+
+    // Publish the current `self.nodes` to every topology subscriber. Must be
+    // called after any mutation of `self.nodes` (see join_apply, leave_apply,
+    // reset, and the write sections of stabilize/fix_fingers, plus the
+    // notify/set_successor/set_predecessor RPC handlers in api.rs).
+    pub fn publish_topology(&self) {
+        self.topology.send_replace(self.nodes.clone());
+    }
+
     // Check if this node is responsible for the given key
     pub fn responsible_for(&self, key: &str) -> bool {
-        in_interval_open_closed(
-            hash_key(key),
-            self.nodes.predecessor.id,
-            self.nodes.me.id,
-        )
+        responsible_for_on(&self.nodes, key)
+    }
+
+    // Every node that replicates a key we're responsible for, besides
+    // ourselves: the members of our successor list, mirroring how Garage's
+    // `walk_ring` returns the `replication_factor` successors of an object's
+    // hash. Every key we own replicates to the same set, so this doesn't
+    // need the key itself - only `responsible_for` does.
+    pub fn replicas_for(&self) -> Vec<Node> {
+        replicas_for_on(&self.nodes)
     }
 
     pub fn closest_preceding_node(&self, id: u64) -> Node {
-        // Search finger table in reverse order for the closest preceding node
-        for finger in self.nodes.finger_table.iter().skip(1).rev() {
-            if in_interval_open_open(finger.node.id, self.nodes.me.id, id) {
-                return finger.node.clone();
-            }
-        }
-        // If none found, return successor (as per Chord protocol)
-        self.nodes.successor.clone()
+        closest_preceding_on(&self.nodes, &self.reliability, id)
     }
 
     // Join a Chord network via a known node (seed node)
     // This performs RPCs without holding locks, then returns the state updates to apply
-    pub async fn join_prepare(&self, seed: NodeAddr) -> ChordResult<Option<(Node, Vec<(usize, Node)>)>> {
+    pub async fn join_prepare(&self, seed: NodeAddr) -> ChordResult<Option<(Node, Vec<(usize, Node)>, Vec<Node>)>> {
 
         // Check if seed node is self
         if seed.label() == self.nodes.me.addr.label() {
@@ -192,43 +338,54 @@ impl ChordNode {
         }
 
         // Successor := n'.find_successor(me.id)
-        let successor = rpc_find_successor(&self.client, &seed, self.nodes.me.id).await?;
-        
+        let successor = rpc_find_successor(&self.client, &self.metrics, &seed, self.nodes.me.id).await?;
+
+        // Seed our successor list from the successor's own list so replication
+        // and stabilize failover have somewhere to start from immediately
+        let mut successor_list = vec![successor.clone()];
+        if let Ok(their_list) = rpc_get_successor_list(&self.client, &self.metrics, &successor.addr).await {
+            successor_list.extend(their_list);
+        }
+        successor_list.truncate(SUCCESSOR_LIST_SIZE);
+
         // Initialize multiple finger table entries on join
-        let id_space_mask = if M == 64 { u64::MAX } else { (1u64 << M) - 1 };
+        let m = config::m();
+        let id_space_mask = if m == 64 { u64::MAX } else { (1u64 << m) - 1 };
         let powers = [2, 4, 8]; // Skip 1 (already done), initialize key fingers
-        
+
         let mut finger_updates = vec![(1, successor.clone())];
-        
+
         for &i in &powers {
-            if i <= M as usize && i < self.nodes.finger_table.len() {
+            if i <= m as usize && i < self.nodes.finger_table.len() {
                 let offset = 1u64 << ((i - 1) as u32);
                 let target_id = (self.nodes.me.id.wrapping_add(offset)) & id_space_mask;
                 
                 // Try to find successor, but don't fail join if this fails
-                if let Ok(finger) = rpc_find_successor(&self.client, &seed, target_id).await {
+                if let Ok(finger) = rpc_find_successor(&self.client, &self.metrics, &seed, target_id).await {
                     finger_updates.push((i, finger));
                 }
             }
         }
-        
+
         // Notify our successor that we might be its predecessor
-        let _ = rpc_notify(&self.client, &successor.addr, &self.nodes.me).await;
-        
-        Ok(Some((successor, finger_updates)))
+        let _ = rpc_notify(&self.client, &self.metrics, &successor.addr, &self.nodes.me).await;
+
+        Ok(Some((successor, finger_updates, successor_list)))
     }
-    
+
     // Apply join state updates (quick, can hold write lock)
-    pub fn join_apply(&mut self, successor: Node, finger_updates: Vec<(usize, Node)>) {
+    pub fn join_apply(&mut self, successor: Node, finger_updates: Vec<(usize, Node)>, successor_list: Vec<Node>) {
         // Update our successor and finger table
         self.nodes.successor = successor.clone();
         self.nodes.predecessor = self.nodes.me.clone();
-        
+        self.nodes.successor_list = successor_list;
+
         for (index, node) in finger_updates {
             if index < self.nodes.finger_table.len() {
                 self.nodes.finger_table[index].node = node;
             }
         }
+        self.publish_topology();
     }
 
     // Gracefully leave the Chord network, performing necessary RPCs without holding locks
@@ -239,8 +396,8 @@ impl ChordNode {
         }
 
         // Notify predecessor and successor to update their pointers, link pred <-> succ
-        rpc_set_successor(&self.client, &self.nodes.predecessor.addr, &self.nodes.successor).await?;
-        rpc_set_predecessor(&self.client, &self.nodes.successor.addr, &self.nodes.predecessor).await?;
+        rpc_set_successor(&self.client, &self.metrics, &self.nodes.predecessor.addr, &self.nodes.successor).await?;
+        rpc_set_predecessor(&self.client, &self.metrics, &self.nodes.successor.addr, &self.nodes.predecessor).await?;
 
         Ok(true)
     }
@@ -250,17 +407,20 @@ impl ChordNode {
         // Reset to single node network
         self.nodes.predecessor = self.nodes.me.clone();
         self.nodes.successor = self.nodes.me.clone();
-        
+        self.nodes.successor_list = vec![self.nodes.me.clone(); SUCCESSOR_LIST_SIZE];
+
         // Reset finger table entries to self
         let me_id = self.nodes.me.id;
         let me_node = self.nodes.me.clone();
-        let id_space_mask = if M == 64 { u64::MAX } else { (1u64 << M) - 1 };
+        let m = config::m();
+        let id_space_mask = if m == 64 { u64::MAX } else { (1u64 << m) - 1 };
 
-        for i in 1..=M {
+        for i in 1..=m {
             let offset = 1u64 << ((i - 1) as u32);
             let start = (me_id.wrapping_add(offset)) & id_space_mask;
             self.nodes.finger_table[i as usize] = FingerEntry { start, node: me_node.clone() };
         }
+        self.publish_topology();
     }
 
     // Reset node to initial single-node state (without notifying other nodes)
@@ -269,13 +429,15 @@ impl ChordNode {
         // Reset to single node network
         self.nodes.predecessor = self.nodes.me.clone();
         self.nodes.successor = self.nodes.me.clone();
-        
+        self.nodes.successor_list = vec![self.nodes.me.clone(); SUCCESSOR_LIST_SIZE];
+
         // Reset finger table entries to self
         let me_id = self.nodes.me.id;
         let me_node = self.nodes.me.clone();
-        let id_space_mask = if M == 64 { u64::MAX } else { (1u64 << M) - 1 };
+        let m = config::m();
+        let id_space_mask = if m == 64 { u64::MAX } else { (1u64 << m) - 1 };
 
-        for i in 1..=M {
+        for i in 1..=m {
             let offset = 1u64 << ((i - 1) as u32);
             let start = (me_id.wrapping_add(offset)) & id_space_mask;
             self.nodes.finger_table[i as usize] = FingerEntry { start, node: me_node.clone() };
@@ -283,130 +445,86 @@ impl ChordNode {
 
         // Reset fix_next counter
         self.fix_next.store(1, Ordering::Relaxed);
+        self.publish_topology();
     }
 
     // --- Periodic maintenance tasks ---
-    // Run the maintenance tasks periodically
+    // Register and schedule the maintenance workers (see worker.rs). Each
+    // runs in its own sequential loop - honoring CrashState and its own
+    // timeout - so registering a new periodic job (successor-list repair,
+    // replica re-sync, ...) is just one more entry in `workers` below rather
+    // than another hand-rolled tokio::spawn closure.
     pub fn maintenance(
         node: std::sync::Arc<tokio::sync::RwLock<Self>>,
         period_ms: u64,
         crash_state: std::sync::Arc<CrashState>,
+        discovery: Option<std::sync::Arc<dyn crate::discovery::Discovery>>,
     ) {
-        // Spawn individual long-running tasks for each maintenance operation
-        // This prevents task explosion and ensures only one of each type runs at a time
-        
-        // Add jitter to prevent all nodes running tasks simultaneously
+        // Jitter so nodes started at the same time don't all tick together;
+        // workers are further staggered relative to each other below.
         use rand::Rng;
-        let jitter_base = rand::thread_rng().gen_range(0..200);
-        
-        // Stabilize task
-        tokio::spawn({
-            let node = std::sync::Arc::clone(&node);
-            let crash_state = std::sync::Arc::clone(&crash_state);
-            let jitter = jitter_base;
-            async move {
-                // Initial jitter delay
-                tokio::time::sleep(Duration::from_millis(jitter)).await;
-                
-                let mut interval = tokio::time::interval(Duration::from_millis(period_ms));
-                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-                loop {
-                    interval.tick().await;
-                    if crash_state.is_crashed() {
-                        continue;
-                    }
-                    
-                    let should_run = {
-                        let guard = node.read().await;
-                        guard.nodes.successor.id != guard.nodes.me.id
-                    };
-                    
-                    if should_run {
-                        let _ = tokio::time::timeout(
-                            Duration::from_secs(10),
-                            ChordNode::stabilize(std::sync::Arc::clone(&node))
-                        ).await;
-                    }
-                }
-            }
-        });
-        
-        // Fix fingers task - offset by 1/3 period
-        tokio::spawn({
-            let node = std::sync::Arc::clone(&node);
-            let crash_state = std::sync::Arc::clone(&crash_state);
-            let jitter = jitter_base + (period_ms / 3);
-            async move {
-                // Initial jitter delay
-                tokio::time::sleep(Duration::from_millis(jitter)).await;
-                
-                let mut interval = tokio::time::interval(Duration::from_millis(period_ms));
-                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-                loop {
-                    interval.tick().await;
-                    if crash_state.is_crashed() {
-                        continue;
-                    }
-                    
-                    let should_run = {
-                        let guard = node.read().await;
-                        guard.nodes.successor.id != guard.nodes.me.id
-                    };
-                    
-                    if should_run {
-                        let _ = tokio::time::timeout(
-                            Duration::from_secs(10),
-                            ChordNode::fix_fingers(std::sync::Arc::clone(&node))
-                        ).await;
-                    }
-                }
-            }
-        });
-        
-        // Check predecessor task - offset by 2/3 period
-        tokio::spawn({
-            let node = std::sync::Arc::clone(&node);
-            let crash_state = std::sync::Arc::clone(&crash_state);
-            let jitter = jitter_base + (2 * period_ms / 3);
-            async move {
-                // Initial jitter delay
-                tokio::time::sleep(Duration::from_millis(jitter)).await;
-                
-                let mut interval = tokio::time::interval(Duration::from_millis(period_ms));
-                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-                loop {
-                    interval.tick().await;
-                    if crash_state.is_crashed() {
-                        continue;
-                    }
-                    
-                    let _ = tokio::time::timeout(
-                        Duration::from_secs(5),
-                        ChordNode::check_predecessor(std::sync::Arc::clone(&node))
-                    ).await;
-                }
-            }
-        });
+        let jitter_base = Duration::from_millis(rand::thread_rng().gen_range(0..200));
+        let period = Duration::from_millis(period_ms);
+
+        let mut workers: Vec<Box<dyn Worker>> = vec![
+            Box::new(StabilizeWorker {
+                node: std::sync::Arc::clone(&node),
+                period,
+                initial_delay: jitter_base,
+            }),
+            Box::new(FixFingersWorker {
+                node: std::sync::Arc::clone(&node),
+                period,
+                initial_delay: jitter_base + period / 3,
+            }),
+            Box::new(CheckPredecessorWorker {
+                node: std::sync::Arc::clone(&node),
+                period,
+                initial_delay: jitter_base + (period / 3) * 2,
+            }),
+        ];
+
+        // Discovery worker - registers with the catalog and re-seeds if we
+        // look isolated (successor == me with a catalog that knows about
+        // peers), offset by half a period so it doesn't line up with the rest.
+        if let Some(discovery) = discovery {
+            workers.push(Box::new(DiscoveryWorker {
+                node: std::sync::Arc::clone(&node),
+                discovery,
+                period: period * 5,
+                initial_delay: jitter_base + period / 2,
+            }));
+        }
+
+        spawn_all(workers, crash_state);
     }
-    
+
     // Stabilize verifies n's immediate successor and tells the successor about n
     // n. stabilize()
     async fn stabilize(node: std::sync::Arc<tokio::sync::RwLock<Self>>) -> ChordResult<()> {
         // Get current node and successor and release lock before RPC
-        let (me, successor, client) = {
+        let (me, successor, client, metrics) = {
             let guard = node.read().await;
-            (guard.nodes.me.clone(), guard.nodes.successor.clone(), guard.client.clone())
+            (guard.nodes.me.clone(), guard.nodes.successor.clone(), guard.client.clone(), Arc::clone(&guard.metrics))
         };
-        
+
         // x = successor.predecessor (RPC call without holding lock)
-        let x_result = rpc_get_predecessor(&client, &successor.addr).await;
+        let x_result = rpc_get_predecessor(&client, &metrics, &successor.addr).await;
         
         // If we can't get predecessor, successor might be down
         let x = match x_result {
             Ok(pred) => pred,
             Err(_) => {
-                // Successor is down, find next alive node in finger table
-                // First get the finger table entries without holding lock during ping
+                // Successor is down. Prefer the successor list (our best knowledge of
+                // who replicates the dead successor's keys) over the finger table,
+                // since promoting from it keeps replica placement coherent.
+                let list_candidates = {
+                    let guard = node.read().await;
+                    guard.nodes.successor_list.iter().skip(1)
+                        .filter(|n| n.id != me.id)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                };
                 let finger_entries = {
                     let guard = node.read().await;
                     guard.nodes.finger_table.iter().skip(2)
@@ -414,28 +532,58 @@ impl ChordNode {
                         .map(|e| e.node.clone())
                         .collect::<Vec<_>>()
                 };
-                
-                // Try to find alive node without holding any lock
+
+                // Try to find alive node without holding any lock, successor list first
                 let mut next_alive: Option<Node> = None;
-                for entry in finger_entries {
-                    if rpc_ping(&client, &entry.addr).await {
-                        next_alive = Some(entry);
+                for entry in list_candidates.iter().chain(finger_entries.iter()) {
+                    if rpc_ping(&client, &metrics, &entry.addr).await {
+                        next_alive = Some(entry.clone());
                         break;
                     }
                 }
-                
-                // Update successor to next alive node or self if none found
-                let mut guard = node.write().await;
-                if let Some(alive_node) = next_alive {
-                    guard.nodes.successor = alive_node.clone();
-                    if guard.nodes.finger_table.len() > 1 {
-                        guard.nodes.finger_table[1].node = alive_node;
+
+                // Refresh the promoted node's own successor list so replication keeps
+                // pointing at live nodes (best-effort; anti-entropy repairs the rest)
+                let refreshed_list = if let Some(alive_node) = &next_alive {
+                    let mut list = vec![alive_node.clone()];
+                    if let Ok(their_list) = rpc_get_successor_list(&client, &metrics, &alive_node.addr).await {
+                        list.extend(their_list);
                     }
+                    list.truncate(SUCCESSOR_LIST_SIZE);
+                    Some(list)
                 } else {
-                    guard.nodes.successor = me.clone();
-                    if guard.nodes.finger_table.len() > 1 {
-                        guard.nodes.finger_table[1].node = me.clone();
-                    }
+                    None
+                };
+
+                // Update successor to next alive node or self if none found
+                let notify_target = {
+                    let mut guard = node.write().await;
+                    let result = if let Some(alive_node) = next_alive {
+                        guard.nodes.successor = alive_node.clone();
+                        if guard.nodes.finger_table.len() > 1 {
+                            guard.nodes.finger_table[1].node = alive_node.clone();
+                        }
+                        if let Some(list) = refreshed_list {
+                            guard.nodes.successor_list = list;
+                        }
+                        Some(alive_node)
+                    } else {
+                        guard.nodes.successor = me.clone();
+                        guard.nodes.successor_list = vec![me.clone(); SUCCESSOR_LIST_SIZE];
+                        if guard.nodes.finger_table.len() > 1 {
+                            guard.nodes.finger_table[1].node = me.clone();
+                        }
+                        None
+                    };
+                    guard.metrics.record_successor_change();
+                    guard.publish_topology();
+                    result
+                };
+
+                // Tell the newly adopted successor we might be its predecessor,
+                // same as the happy path below does (without holding any lock)
+                if let Some(alive_node) = notify_target {
+                    let _ = rpc_notify(&client, &metrics, &alive_node.addr, &me).await;
                 }
                 return Ok(());
             }
@@ -444,7 +592,7 @@ impl ChordNode {
         // Update state - determine if we need to update successor
         let (should_update, new_successor, current_successor, me_clone) = {
             let guard = node.read().await;
-            let should_update = in_interval_open_open(x.id, me.id, successor.id);
+            let should_update = in_interval_open_open(x.id, me.id, successor.id, config::m());
             let new_succ = if should_update { x.clone() } else { successor.clone() };
             let curr_succ = guard.nodes.successor.clone();
             (should_update, new_succ, curr_succ, me.clone())
@@ -452,27 +600,38 @@ impl ChordNode {
         
         // Apply update if needed (quick write lock)
         if should_update || current_successor.id != new_successor.id {
+            // Pull the successor's own successor list (RPC, no lock held) so ours
+            // stays one hop behind it, keeping replica placement consistent
+            let mut refreshed_list = vec![new_successor.clone()];
+            if let Ok(their_list) = rpc_get_successor_list(&client, &metrics, &new_successor.addr).await {
+                refreshed_list.extend(their_list);
+            }
+            refreshed_list.truncate(SUCCESSOR_LIST_SIZE);
+
             let mut guard = node.write().await;
             guard.nodes.successor = new_successor.clone();
+            guard.nodes.successor_list = refreshed_list;
             if guard.nodes.finger_table.len() > 1 {
                 guard.nodes.finger_table[1].node = new_successor.clone();
             }
+            guard.metrics.record_successor_change();
+            guard.publish_topology();
         }
-        
+
         // Notify successor WITHOUT holding any lock
-        let _ = rpc_notify(&client, &new_successor.addr, &me_clone).await;
-        
+        let _ = rpc_notify(&client, &metrics, &new_successor.addr, &me_clone).await;
+
         Ok(())
     }
     
     // Fix finger table entries. Next stores the index of the next finger to fix.
     // n. fix_fingers()
     async fn fix_fingers(node: std::sync::Arc<tokio::sync::RwLock<Self>>) -> ChordResult<()> {
-        let m = M as usize;
-        
+        let m = config::m() as usize;
+
         for _ in 0..2 {
             // Get data and increment counter - use read lock for most of this
-            let (me_id, successor_node, seed, next, client) = {
+            let (me_id, successor_node, seed, next, client, metrics) = {
                 let guard = node.read().await;
                 // next := next + 1 ; if next > m then next := 1
                 let mut next = guard.fix_next.load(Ordering::Relaxed) + 1;
@@ -480,32 +639,34 @@ impl ChordNode {
                     next = 1;
                 }
                 guard.fix_next.store(next, Ordering::Relaxed);
-                
+
                 // Current node info
                 let seed = guard.nodes.successor.addr.clone();
                 let successor = guard.nodes.successor.clone();
                 let client = guard.client.clone();
-                (guard.nodes.me.id, successor, seed, next, client)
+                (guard.nodes.me.id, successor, seed, next, client, Arc::clone(&guard.metrics))
             };
-            
+
             // finger[next] := find_successor(n + 2^(next-1)) (without holding lock)
-            let id_space_mask = if M == 64 { u64::MAX } else { (1u64 << M) - 1 };
+            let id_space_mask = if m as u32 == 64 { u64::MAX } else { (1u64 << m) - 1 };
             let offset = 1u64 << ((next - 1) as u32);
             let start = (me_id.wrapping_add(offset)) & id_space_mask;
-            
+
             // Try to find successor, but handle failures gracefully
-            let finger_node = match rpc_find_successor(&client, &seed, start).await {
+            let finger_node = match rpc_find_successor(&client, &metrics, &seed, start).await {
                 Ok(node) => node,
                 Err(_) => {
                     // Failed to find successor (dead nodes in chain), use successor
                     successor_node.clone()
                 }
             };
-            
+
             // Update finger table
             let mut guard = node.write().await;
             guard.nodes.finger_table[next].start = start;
             guard.nodes.finger_table[next].node = finger_node;
+            guard.metrics.record_finger_refresh();
+            guard.publish_topology();
         }
         
         Ok(())
@@ -515,25 +676,27 @@ impl ChordNode {
     // n. check_predecessor()
     async fn check_predecessor(node: std::sync::Arc<tokio::sync::RwLock<Self>>) -> ChordResult<()> {
         // Get current node and predecessor without holding lock during RPC
-        let (me, predecessor, client) = {
+        let (me, predecessor, client, metrics) = {
             let guard = node.read().await;
-            (guard.nodes.me.clone(), guard.nodes.predecessor.clone(), guard.client.clone())
+            (guard.nodes.me.clone(), guard.nodes.predecessor.clone(), guard.client.clone(), Arc::clone(&guard.metrics))
         };
-        
+
         // If predecessor is self, nothing to check
         if predecessor.id == me.id {
             return Ok(());
         }
-        
+
         // Check if predecessor is alive (without holding lock)
-        let alive = rpc_ping(&client, &predecessor.addr).await;
-        
+        let alive = rpc_ping(&client, &metrics, &predecessor.addr).await;
+
         // Update predecessor if it's dead
         if !alive {
             let mut guard = node.write().await;
             // Double-check predecessor hasn't changed while we were checking
             if guard.nodes.predecessor.id == predecessor.id {
                 guard.nodes.predecessor = me;
+                guard.metrics.record_predecessor_change();
+                guard.publish_topology();
             }
         }
 
@@ -542,75 +705,255 @@ impl ChordNode {
 
 }
 
+// --- Maintenance workers (see worker.rs for the scheduler) ---
+
+struct StabilizeWorker {
+    node: Arc<tokio::sync::RwLock<ChordNode>>,
+    period: Duration,
+    initial_delay: Duration,
+}
+
+#[async_trait]
+impl Worker for StabilizeWorker {
+    fn name(&self) -> &'static str {
+        "stabilize"
+    }
+
+    fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    fn interval(&self) -> Duration {
+        self.period
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    async fn work(&self) {
+        // Stabilizing a single-node ring is a no-op, so skip it entirely.
+        let should_run = {
+            let guard = self.node.read().await;
+            guard.nodes.successor.id != guard.nodes.me.id
+        };
+        if should_run {
+            let _ = ChordNode::stabilize(Arc::clone(&self.node)).await;
+        }
+    }
+}
+
+struct FixFingersWorker {
+    node: Arc<tokio::sync::RwLock<ChordNode>>,
+    period: Duration,
+    initial_delay: Duration,
+}
+
+#[async_trait]
+impl Worker for FixFingersWorker {
+    fn name(&self) -> &'static str {
+        "fix_fingers"
+    }
+
+    fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    fn interval(&self) -> Duration {
+        self.period
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    async fn work(&self) {
+        let should_run = {
+            let guard = self.node.read().await;
+            guard.nodes.successor.id != guard.nodes.me.id
+        };
+        if should_run {
+            let _ = ChordNode::fix_fingers(Arc::clone(&self.node)).await;
+        }
+    }
+}
+
+struct CheckPredecessorWorker {
+    node: Arc<tokio::sync::RwLock<ChordNode>>,
+    period: Duration,
+    initial_delay: Duration,
+}
+
+#[async_trait]
+impl Worker for CheckPredecessorWorker {
+    fn name(&self) -> &'static str {
+        "check_predecessor"
+    }
+
+    fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    fn interval(&self) -> Duration {
+        self.period
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(5)
+    }
+
+    async fn work(&self) {
+        let _ = ChordNode::check_predecessor(Arc::clone(&self.node)).await;
+    }
+}
+
+struct DiscoveryWorker {
+    node: Arc<tokio::sync::RwLock<ChordNode>>,
+    discovery: Arc<dyn crate::discovery::Discovery>,
+    period: Duration,
+    initial_delay: Duration,
+}
+
+#[async_trait]
+impl Worker for DiscoveryWorker {
+    fn name(&self) -> &'static str {
+        "discovery"
+    }
+
+    fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    fn interval(&self) -> Duration {
+        self.period
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    async fn work(&self) {
+        let me = { self.node.read().await.nodes.me.addr.clone() };
+        self.discovery.register(&me).await;
+
+        let isolated = {
+            let guard = self.node.read().await;
+            guard.nodes.successor.id == guard.nodes.me.id
+        };
+        if !isolated {
+            return;
+        }
+
+        let peers = self.discovery.peers().await;
+        if let Some(seed) = peers.into_iter().find(|addr| addr.label() != me.label()) {
+            let join_result = {
+                let guard = self.node.read().await;
+                guard.join_prepare(seed).await
+            };
+            if let Ok(Some((successor, finger_updates, successor_list))) = join_result {
+                let mut guard = self.node.write().await;
+                guard.join_apply(successor, finger_updates, successor_list);
+            }
+        }
+    }
+}
+
 // --- RPC methods to interact with other nodes ---
 
 // Ping another node to check if it's alive
 // Returns false if node is crashed (503) or unreachable
-async fn rpc_ping(client: &Client, node: &NodeAddr) -> bool {
+async fn rpc_ping(client: &Client, metrics: &Metrics, node: &NodeAddr) -> bool {
     let url = format!("{}/internal/ping", node.to_url());
-    match client.get(url).send().await {
-        Ok(response) => {
-            let status = response.status();
-            // Node is alive only if status is 200-299 and not 503
-            status.is_success() && status != 503
-        },
-        Err(_) => false,
-    }
-}
-
-// Find the successor for the current node
-async fn rpc_get_successor(client: &Client, node: &NodeAddr) -> ChordResult<Node> {
-    let url = format!("{}/internal/successor", node.to_url());
-    let response = client.get(&url).send().await?;
-    let successor = response.json::<Node>().await?;
-    Ok(successor)
+    let result = timed_rpc(metrics, RpcKind::Ping, async {
+        match client.get(url).send().await {
+            Ok(response) => {
+                let status = response.status();
+                // Node is alive only if status is 200-299 and not 503
+                if status.is_success() && status != 503 { Ok(()) } else { Err(()) }
+            }
+            Err(_) => Err(()),
+        }
+    })
+    .await;
+    result.is_ok()
 }
 
 // Find the predecessor for the current node
-async fn rpc_get_predecessor(client: &Client, node: &NodeAddr) -> ChordResult<Node> {
+async fn rpc_get_predecessor(client: &Client, metrics: &Metrics, node: &NodeAddr) -> ChordResult<Node> {
     let url = format!("{}/internal/predecessor", node.to_url());
-    let response = client.get(&url).send().await?;
-    
-    // Check for 503 (crashed node)
-    if response.status() == 503 {
-        return Err("Node is crashed (503)".into());
-    }
-    
-    let predecessor = response.json::<Node>().await?;
-    Ok(predecessor)
+    timed_rpc(metrics, RpcKind::GetPredecessor, async {
+        let response = client.get(&url).send().await?;
+
+        // Check for 503 (crashed node)
+        if response.status() == 503 {
+            return Err("Node is crashed (503)".into());
+        }
+
+        let predecessor = response.json::<Node>().await?;
+        Ok(predecessor)
+    })
+    .await
+}
+
+// Fetch a node's successor list (used to seed/refresh our own on join and stabilize)
+async fn rpc_get_successor_list(client: &Client, metrics: &Metrics, node: &NodeAddr) -> ChordResult<Vec<Node>> {
+    let url = format!("{}/internal/successor-list", node.to_url());
+    timed_rpc(metrics, RpcKind::GetSuccessorList, async {
+        let response = client.get(&url).send().await?;
+
+        if response.status() == 503 {
+            return Err("Node is crashed (503)".into());
+        }
+
+        let successor_list = response.json::<Vec<Node>>().await?;
+        Ok(successor_list)
+    })
+    .await
 }
 
 // Find the successor for a given node ID
-async fn rpc_find_successor(client: &Client, seed: &NodeAddr, id: u64) -> ChordResult<Node> {
+async fn rpc_find_successor(client: &Client, metrics: &Metrics, seed: &NodeAddr, id: u64) -> ChordResult<Node> {
     let url = format!("{}/internal/find-successor?id={}&hops=0", seed.to_url(), id);
-    let response = client.get(url).send().await?;
-    
-    // Check for 503 (crashed node)
-    if response.status() == 503 {
-        return Err("Node is crashed (503)".into());
-    }
-    
-    let successor = response.json::<Node>().await?;
-    Ok(successor)
-} 
+    timed_rpc(metrics, RpcKind::FindSuccessor, async {
+        let response = client.get(url).send().await?;
+
+        // Check for 503 (crashed node)
+        if response.status() == 503 {
+            return Err("Node is crashed (503)".into());
+        }
+
+        let successor = response.json::<Node>().await?;
+        Ok(successor)
+    })
+    .await
+}
 
 // Notify a node that we might be its predecessor
-async fn rpc_notify(client: &Client, node: &NodeAddr, me: &Node) -> ChordResult <()> {
+async fn rpc_notify(client: &Client, metrics: &Metrics, node: &NodeAddr, me: &Node) -> ChordResult<()> {
     let url = format!("{}/internal/notify", node.to_url());
-    client.post(&url).json(me).send().await?;
-    Ok(())
+    timed_rpc(metrics, RpcKind::Notify, async {
+        client.post(&url).json(me).send().await?;
+        Ok(())
+    })
+    .await
 }
 
 // Set the successor of a node
-async fn rpc_set_successor(client: &Client, node: &NodeAddr, successor: &Node) -> ChordResult<()> {
+async fn rpc_set_successor(client: &Client, metrics: &Metrics, node: &NodeAddr, successor: &Node) -> ChordResult<()> {
     let url = format!("{}/internal/set-successor", node.to_url());
-    client.post(&url).json(successor).send().await?;
-    Ok(())
+    timed_rpc(metrics, RpcKind::SetSuccessor, async {
+        client.post(&url).json(successor).send().await?;
+        Ok(())
+    })
+    .await
 }
 
 // Set the predecessor of a node
-async fn rpc_set_predecessor(client: &Client, node: &NodeAddr, predecessor: &Node) -> ChordResult<()> {
+async fn rpc_set_predecessor(client: &Client, metrics: &Metrics, node: &NodeAddr, predecessor: &Node) -> ChordResult<()> {
     let url = format!("{}/internal/set-predecessor", node.to_url());
-    client.post(&url).json(predecessor).send().await?;
-    Ok(())  
+    timed_rpc(metrics, RpcKind::SetPredecessor, async {
+        client.post(&url).json(predecessor).send().await?;
+        Ok(())
+    })
+    .await
 }
\ No newline at end of file
@@ -1,6 +1,216 @@
-// The size of the identifier circle (2^M)
-// Meaning we use M-bit identifiers (u64)
-pub const M: u32 = 16; // 16 bits = 2^16 identifiers (65536 possible IDs)
-pub const HOP_LIMIT: u32 = 32;
-pub const IDLE_LIMIT: u64 = 10; // in minutes
-pub const MAINTENANCE_INTERVAL_MS: u64 = 1000; // 1 second
\ No newline at end of file
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+pub const SUCCESSOR_LIST_SIZE: usize = 3; // number of successors replicated to for fault tolerance
+// How long a request is allowed to run before RequestDeadline middleware
+// short-circuits it with 408 Request Timeout. Covers several 1000ms
+// forward_get/forward_put hops plus lock contention on notify/set-successor;
+// a chain that waits out the full 1000ms at every one of its hop_limit()
+// hops (32 by default) runs past it and gets the 408.
+pub const REQUEST_DEADLINE_MS: u64 = 10_000;
+// How long actix is allowed to wait, after a graceful shutdown starts, for
+// in-flight requests to finish before it cuts them off - see shutdown.rs.
+// Generous enough to cover a slow forward_put_stream chain, but short enough
+// that SIGTERM from an orchestrator (which gives its own bounded grace
+// period before SIGKILL) doesn't get raced.
+pub const SHUTDOWN_GRACE_PERIOD_SECS: u64 = 30;
+// Tuning for the pooled reqwest clients ChordNode builds once at startup and
+// shares (via AppState::client/stream_client) across every inter-node RPC,
+// rather than each call opening its own TCP connection - see ChordNode::new.
+// maintenance_interval_ms() fires every second by default, so without reuse every node
+// would redo a TCP+TLS handshake with the same handful of neighbors on every
+// tick.
+pub const CONNECTION_POOL_MAX_IDLE_PER_HOST: usize = 10;
+pub const CONNECTION_POOL_IDLE_TIMEOUT_SECS: u64 = 30;
+
+const DEFAULT_HOP_LIMIT: u32 = 32;
+const DEFAULT_IDLE_LIMIT: u64 = 10; // in minutes
+const DEFAULT_MAINTENANCE_INTERVAL_MS: u64 = 1000; // 1 second
+// The size of the identifier circle (2^M), i.e. M-bit identifiers. 16 bits =
+// 2^16 identifiers (65536 possible IDs) - small enough to keep finger tables
+// and test rings cheap, large enough that collisions aren't a day-to-day
+// concern.
+const DEFAULT_M: u32 = 16;
+
+fn default_hop_limit() -> u32 {
+    DEFAULT_HOP_LIMIT
+}
+
+fn default_idle_limit() -> u64 {
+    DEFAULT_IDLE_LIMIT
+}
+
+fn default_maintenance_interval_ms() -> u64 {
+    DEFAULT_MAINTENANCE_INTERVAL_MS
+}
+
+fn default_m() -> u32 {
+    DEFAULT_M
+}
+
+// Which hasher utils::hash_key/hash_bytes use to derive an identifier. SHA-1
+// is the default (matches the original hardwired behavior); SHA-256 trades
+// speed for a larger security margin, and Blake3 is for experiments that
+// want hashing itself to stop being the bottleneck. Only the leading bits of
+// whichever digest comes out are kept anyway (see utils::mask_to_width), so
+// swapping algorithms never changes how wide an identifier is - that's `m`'s
+// job, independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Blake3,
+}
+
+impl Default for HashAlgorithm {
+    fn default() -> Self {
+        HashAlgorithm::Sha1
+    }
+}
+
+fn default_hash_algorithm() -> HashAlgorithm {
+    HashAlgorithm::default()
+}
+
+// Operator-tunable knobs that used to be the plain constants above, now
+// loaded once at startup from an optional TOML file (see `init`) instead of
+// baked in at compile time - retuning the hop bound, maintenance cadence,
+// ring width, or hash strength for a differently-sized deployment no longer
+// needs a rebuild.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuntimeConfig {
+    #[serde(default = "default_hop_limit")]
+    pub hop_limit: u32,
+    #[serde(default = "default_idle_limit")]
+    pub idle_limit: u64,
+    #[serde(default = "default_maintenance_interval_ms")]
+    pub maintenance_interval_ms: u64,
+    #[serde(default = "default_m")]
+    pub m: u32,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: HashAlgorithm,
+    // PEM paths for this node's TLS identity and the CA its RPC client
+    // should trust - see tls.rs. None (the default) means plaintext HTTP,
+    // unchanged from before TLS support existed.
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+    #[serde(default)]
+    pub tls_ca_path: Option<String>,
+    // Generate and bind an ephemeral self-signed cert instead of reading
+    // tls_cert_path/tls_key_path - see tls::dev_self_signed_config. Meant for
+    // a one-machine simulation, not a real deployment.
+    #[serde(default)]
+    pub tls_dev_self_signed: bool,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        RuntimeConfig {
+            hop_limit: DEFAULT_HOP_LIMIT,
+            idle_limit: DEFAULT_IDLE_LIMIT,
+            maintenance_interval_ms: DEFAULT_MAINTENANCE_INTERVAL_MS,
+            m: DEFAULT_M,
+            hash_algorithm: HashAlgorithm::default(),
+            tls_cert_path: None,
+            tls_key_path: None,
+            tls_ca_path: None,
+            tls_dev_self_signed: false,
+        }
+    }
+}
+
+static RUNTIME_CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+
+// Load `path` (TOML) if it exists and parses cleanly, falling back to
+// defaults otherwise - called once from main::main before the server starts
+// accepting connections. A missing file is the common case (no operator
+// override yet) and stays quiet; a present-but-unparseable one is loud,
+// since that's almost always a typo worth surfacing rather than silently
+// ignoring.
+pub fn init(path: &str) {
+    let mut resolved = match std::fs::read_to_string(path) {
+        Ok(contents) => match toml::from_str(&contents) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("Failed to parse config file {}: {} - using defaults", path, e);
+                RuntimeConfig::default()
+            }
+        },
+        Err(_) => RuntimeConfig::default(),
+    };
+    validate_m(&mut resolved);
+    // `--tls-dev` on the command line turns on tls_dev_self_signed without
+    // needing a config file.
+    if std::env::args().any(|a| a == "--tls-dev") {
+        resolved.tls_dev_self_signed = true;
+    }
+    // Only the first call wins; main only ever calls this once at startup.
+    let _ = RUNTIME_CONFIG.set(resolved);
+}
+
+// `m` drives a `raw >> (64 - m)` shift in utils::mask_to_width and a
+// `1u64 << m` id_space_mask at every finger-table site in chord.rs, neither
+// of which tolerates `m` outside `1..=64` - `m == 0` shifts by 64 (a panic in
+// debug, silently unmasked ids in release, by Rust's shift-amount-mod-64
+// rule) and `m > 64` overflows the mask. Reject an out-of-range value the
+// same way an unparseable file is rejected: loud on stderr, then fall back
+// to the default rather than letting a typo'd TOML value panic the ring on
+// its first hashed key.
+fn validate_m(config: &mut RuntimeConfig) {
+    if !(1..=64).contains(&config.m) {
+        eprintln!("Invalid m = {} in config file (must be in 1..=64) - using default {}", config.m, DEFAULT_M);
+        config.m = DEFAULT_M;
+    }
+}
+
+fn runtime() -> &'static RuntimeConfig {
+    RUNTIME_CONFIG.get_or_init(RuntimeConfig::default)
+}
+
+pub fn hop_limit() -> u32 {
+    runtime().hop_limit
+}
+
+pub fn idle_limit() -> u64 {
+    runtime().idle_limit
+}
+
+pub fn maintenance_interval_ms() -> u64 {
+    runtime().maintenance_interval_ms
+}
+
+pub fn m() -> u32 {
+    runtime().m
+}
+
+pub fn hash_algorithm() -> HashAlgorithm {
+    runtime().hash_algorithm
+}
+
+pub fn tls_cert_path() -> Option<String> {
+    runtime().tls_cert_path.clone()
+}
+
+pub fn tls_key_path() -> Option<String> {
+    runtime().tls_key_path.clone()
+}
+
+pub fn tls_ca_path() -> Option<String> {
+    runtime().tls_ca_path.clone()
+}
+
+pub fn tls_dev_self_signed() -> bool {
+    runtime().tls_dev_self_signed
+}
+
+// Whether this node should bind HTTPS instead of plaintext HTTP and have its
+// peers addressed as such - see tls::server_config and NodeAddr::to_url.
+// True for tls_dev_self_signed, or once both a real cert and key path are
+// configured; tls_ca_path alone only affects which CA the RPC client trusts,
+// not whether TLS is on.
+pub fn tls_enabled() -> bool {
+    runtime().tls_dev_self_signed || (runtime().tls_cert_path.is_some() && runtime().tls_key_path.is_some())
+}
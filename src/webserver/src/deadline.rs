@@ -0,0 +1,116 @@
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpResponse,
+};
+use std::future::{ready, Ready};
+use std::time::Duration;
+
+use crate::activity::ActivityTimer;
+
+// Internal Chord RPC endpoints are exempt from the deadline: stabilize/
+// fix_fingers/check_predecessor already retry on their own schedule, and
+// cutting one off mid-call would just make that retry loop noisier instead
+// of protecting anything.
+fn is_exempt(path: &str) -> bool {
+    path.starts_with("/internal/")
+}
+
+/// Middleware factory enforcing a per-request deadline (config::REQUEST_DEADLINE_MS).
+/// Requests that don't complete in time get a 408 instead of hanging the
+/// client forever - forward_get/forward_put can chain multiple 1000ms hops,
+/// and notify/set-successor race on write-lock acquisition, so without this
+/// a slow chain of hops had no upper bound at all. Also touches the
+/// ActivityTimer once per served request, replacing the ad hoc wrap_fn that
+/// used to do this in main.rs.
+pub struct RequestDeadline {
+    deadline: Duration,
+    activity: ActivityTimer,
+}
+
+impl RequestDeadline {
+    pub fn new(deadline_ms: u64, activity: ActivityTimer) -> Self {
+        RequestDeadline { deadline: Duration::from_millis(deadline_ms), activity }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestDeadline
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestDeadlineMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestDeadlineMiddleware {
+            service,
+            deadline: self.deadline,
+            activity: self.activity.clone(),
+        }))
+    }
+}
+
+pub struct RequestDeadlineMiddleware<S> {
+    service: S,
+    deadline: Duration,
+    activity: ActivityTimer,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestDeadlineMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = std::pin::Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        self.activity.touch();
+
+        if is_exempt(req.path()) {
+            let fut = self.service.call(req);
+            return Box::pin(async move {
+                let res = fut.await?;
+                Ok(res.map_into_left_body())
+            });
+        }
+
+        let deadline = self.deadline;
+        // Clone the (Rc-backed) HttpRequest before handing `req` to the
+        // inner service, so we still have something to build a
+        // ServiceResponse from if the deadline wins the race.
+        let http_req = req.request().clone();
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            match tokio::time::timeout(deadline, fut).await {
+                Ok(res) => Ok(res?.map_into_left_body()),
+                Err(_) => {
+                    let response = HttpResponse::RequestTimeout().body("Request exceeded deadline");
+                    Ok(ServiceResponse::new(http_req, response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_rpc_endpoints_are_exempt() {
+        assert!(is_exempt("/internal/ping"));
+        assert!(is_exempt("/internal/find-successor"));
+        assert!(!is_exempt("/storage/foo"));
+        assert!(!is_exempt("/node-info"));
+    }
+}
@@ -0,0 +1,137 @@
+// Pluggable peer discovery, modeled on Garage's consul.rs: a node periodically
+// registers itself with an external catalog and asks it for the current set
+// of live peers, so join_prepare can pick a seed automatically instead of
+// requiring a hard-coded one, and the maintenance loop can re-seed after a
+// network partition heals.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::chord::NodeAddr;
+
+#[async_trait]
+pub trait Discovery: Send + Sync {
+    // Tell the catalog this node is alive and reachable at `me`. A no-op for
+    // backends (like the static file) that don't support registration.
+    async fn register(&self, me: &NodeAddr);
+
+    // The catalog's current view of live peers, for picking a join seed.
+    async fn peers(&self) -> Vec<NodeAddr>;
+}
+
+// Registers with a local Consul agent's HTTP API and reads back the peers
+// Consul's own health checks (an HTTP check against /internal/ping) consider
+// passing for the service.
+pub struct ConsulDiscovery {
+    agent_url: String,
+    service_name: String,
+    client: Client,
+}
+
+impl ConsulDiscovery {
+    pub fn new(agent_url: String, service_name: String) -> Self {
+        ConsulDiscovery { agent_url, service_name, client: Client::new() }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ConsulCheck {
+    #[serde(rename = "HTTP")]
+    http: String,
+    #[serde(rename = "Interval")]
+    interval: String,
+}
+
+#[derive(serde::Serialize)]
+struct ConsulRegistration {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Address")]
+    address: String,
+    #[serde(rename = "Port")]
+    port: u16,
+    #[serde(rename = "Check")]
+    check: ConsulCheck,
+}
+
+#[derive(Deserialize)]
+struct ConsulHealthEntry {
+    #[serde(rename = "Service")]
+    service: ConsulHealthService,
+}
+
+#[derive(Deserialize)]
+struct ConsulHealthService {
+    #[serde(rename = "Address")]
+    address: String,
+    #[serde(rename = "Port")]
+    port: u16,
+}
+
+#[async_trait]
+impl Discovery for ConsulDiscovery {
+    async fn register(&self, me: &NodeAddr) {
+        let registration = ConsulRegistration {
+            id: me.label(),
+            name: self.service_name.clone(),
+            address: me.host.clone(),
+            port: me.port,
+            check: ConsulCheck {
+                http: format!("{}/internal/ping", me.to_url()),
+                interval: "10s".to_string(),
+            },
+        };
+        let url = format!("{}/v1/agent/service/register", self.agent_url);
+        let _ = self.client.put(&url).json(&registration).send().await;
+    }
+
+    async fn peers(&self) -> Vec<NodeAddr> {
+        let url = format!("{}/v1/health/service/{}?passing=true", self.agent_url, self.service_name);
+        let Ok(resp) = self.client.get(&url).send().await else {
+            return Vec::new();
+        };
+        let Ok(entries) = resp.json::<Vec<ConsulHealthEntry>>().await else {
+            return Vec::new();
+        };
+        entries
+            .into_iter()
+            .map(|e| NodeAddr { host: e.service.address, port: e.service.port })
+            .collect()
+    }
+}
+
+// Fallback for deployments without a Consul agent: a plain text file of
+// `host:port` lines, one per known peer, read fresh on every lookup so
+// operators can update it without restarting any node. Registration is a
+// no-op since there's nowhere to write to.
+pub struct StaticFileDiscovery {
+    path: String,
+}
+
+impl StaticFileDiscovery {
+    pub fn new(path: String) -> Self {
+        StaticFileDiscovery { path }
+    }
+}
+
+#[async_trait]
+impl Discovery for StaticFileDiscovery {
+    async fn register(&self, _me: &NodeAddr) {}
+
+    async fn peers(&self) -> Vec<NodeAddr> {
+        let Ok(contents) = tokio::fs::read_to_string(&self.path).await else {
+            return Vec::new();
+        };
+        contents
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let (host, port) = line.rsplit_once(':')?;
+                Some(NodeAddr { host: host.to_string(), port: port.parse().ok()? })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Tracks whether this node is in the middle of a graceful departure from the
+// ring (see api::post_leave). Similar in spirit to simulate::CrashState, but
+// deliberately narrower: it only gates fresh writes this node would store
+// locally, not every request, since reads and in-flight forwards have to
+// keep working while the departing node's keys are handed off to its
+// successor. If the handoff fails partway through, the flag is cleared again
+// so the node keeps serving normally instead of being stuck unavailable.
+pub struct DrainState {
+    draining: AtomicBool,
+}
+
+impl DrainState {
+    pub fn new() -> Self {
+        DrainState {
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    // Start gating fresh local writes ahead of a key handoff.
+    pub fn begin(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    // Stop gating, whether the departure finished or was aborted.
+    pub fn finish(&self) {
+        self.draining.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for DrainState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drain_state() {
+        let state = DrainState::new();
+        assert!(!state.is_draining());
+
+        state.begin();
+        assert!(state.is_draining());
+
+        state.finish();
+        assert!(!state.is_draining());
+    }
+}
@@ -0,0 +1,81 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::Serialize;
+use thiserror::Error;
+
+// Centralizes the error-to-HTTP mapping that used to be scattered across
+// every forwarding call site as a hand-built `HttpResponse::BadGateway().
+// body(format!(...))` (or BadRequest/RequestTimeout with its own wording).
+// Handlers and the `forward_*` helpers return `Result<_, ChordError>` now,
+// so the status code and body shape only have to be gotten right once, here,
+// and integration tests can match on a variant instead of scraping a string.
+#[derive(Debug, Error)]
+pub enum ChordError {
+    #[error("chord hop limit exceeded")]
+    HopLimitExceeded,
+
+    #[error("forward to {target} failed: {source}")]
+    ForwardFailed { target: String, source: reqwest::Error },
+
+    #[error("invalid node address: {0}")]
+    InvalidNodeAddr(String),
+
+    #[error("misrouted request: {0}")]
+    Misrouted(String),
+
+    #[error("timed out acquiring lock")]
+    LockTimeout,
+
+    #[error("node is simulating a crash")]
+    UpstreamCrashed,
+
+    #[error("node is leaving the ring, retry against its successor")]
+    Draining,
+
+    #[error("bad payload: {0}")]
+    BadPayload(String),
+
+    // Catch-all for the boxed chord-level errors join_prepare/leave_prepare
+    // already return (chord::ChordResult) - those come from a handful of
+    // different RPC helpers, so there's no single typed `source` to carry
+    // the way ForwardFailed does.
+    #[error("{0}")]
+    Internal(String),
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+}
+
+impl ResponseError for ChordError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ChordError::HopLimitExceeded => StatusCode::BAD_GATEWAY,
+            ChordError::ForwardFailed { .. } => StatusCode::BAD_GATEWAY,
+            ChordError::InvalidNodeAddr(_) => StatusCode::BAD_REQUEST,
+            ChordError::Misrouted(_) => StatusCode::BAD_GATEWAY,
+            ChordError::LockTimeout => StatusCode::REQUEST_TIMEOUT,
+            ChordError::UpstreamCrashed => StatusCode::SERVICE_UNAVAILABLE,
+            ChordError::Draining => StatusCode::SERVICE_UNAVAILABLE,
+            ChordError::Internal(_) => StatusCode::BAD_GATEWAY,
+            ChordError::BadPayload(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorBody { error: &self.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes_match_variant_intent() {
+        assert_eq!(ChordError::HopLimitExceeded.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(ChordError::LockTimeout.status_code(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(ChordError::UpstreamCrashed.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(ChordError::BadPayload("x".to_string()).status_code(), StatusCode::BAD_REQUEST);
+    }
+}
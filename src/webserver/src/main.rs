@@ -7,30 +7,86 @@ mod simulate;
 mod network;
 mod utils;
 mod config;
+mod merkle;
+mod anti_entropy;
+mod reliability;
+mod secure_transport;
+mod secure_state;
+mod discovery;
+mod metrics;
+mod worker;
+mod deadline;
+mod draining;
+mod error;
+mod shutdown;
+mod tls;
 
 // Import everything we need from our modules
 use storage::Storage;
 use activity::ActivityTimer;
-use chord::{NodeAddr, ChordNode};
+use chord::{NodeAddr, ChordNode, KnownNodes};
+use deadline::RequestDeadline;
+use draining::DrainState;
+use metrics::RequestCounters;
+use reliability::ReliabilityTracker;
 use simulate::{CrashState, CrashSimulator};
-use config::{IDLE_LIMIT, MAINTENANCE_INTERVAL_MS};
+use secure_state::SecureState;
+use config::{REQUEST_DEADLINE_MS, SHUTDOWN_GRACE_PERIOD_SECS};
 
 // Import everything we need from external crates
-use actix_web::dev::Service;
 use actix_web::{App, HttpServer, web};
+use reqwest::Client;
 use std::env::args;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 
 struct AppState {
     storage: RwLock<Storage>,
     chord: SharedChordHolder,
+    // Lock-free topology snapshot and the state routing needs alongside it,
+    // cloned once at startup so hot read paths (storage GET/PUT/DELETE
+    // routing) never have to take `chord`'s RwLock at all - see
+    // ChordNode::subscribe.
+    topology: watch::Receiver<KnownNodes>,
+    reliability: Arc<ReliabilityTracker>,
+    client: Client,
+    // Connect-timeout-only client for streamed storage forwarding, cloned
+    // once at startup alongside `client` - see ChordNode::new.
+    stream_client: Client,
     activity: ActivityTimer,
     crash_state: Arc<CrashState>,
+    secure: Arc<SecureState>,
+    // Lock-free per-request counters bumped from get_storage/put_storage,
+    // rendered alongside ChordNode's Metrics at GET /metrics (see
+    // metrics::RequestCounters).
+    request_counters: RequestCounters,
+    // Set while post_leave is handing off our keys to our successor, so
+    // put_storage/delete_storage can reject fresh local writes instead of
+    // losing them to the handoff's storage.clear() (see draining::DrainState).
+    draining: Arc<DrainState>,
 }
 
 type SharedChordHolder = Arc<RwLock<ChordNode>>;
 
+// Look up the value following a `--flag value` pair in the process arguments.
+fn flag_value(flag: &str) -> Option<String> {
+    let argv: Vec<String> = args().collect();
+    argv.iter().position(|a| a == flag).and_then(|i| argv.get(i + 1)).cloned()
+}
+
+// Build the discovery backend from CLI flags, if any were given: `--consul
+// <agent-url>` for a live Consul agent, `--seed-file <path>` for the static
+// fallback. Neither flag means no discovery - joining stays manual, as today.
+fn get_discovery() -> Option<Arc<dyn discovery::Discovery>> {
+    if let Some(agent_url) = flag_value("--consul") {
+        return Some(Arc::new(discovery::ConsulDiscovery::new(agent_url, "inf3200-chord".to_string())));
+    }
+    if let Some(path) = flag_value("--seed-file") {
+        return Some(Arc::new(discovery::StaticFileDiscovery::new(path)));
+    }
+    None
+}
+
 // Fetch host configuration based on process arguments
 fn get_config() -> NodeAddr {
     // Get the command line arguments
@@ -59,48 +115,88 @@ fn get_config() -> NodeAddr {
     NodeAddr { host, port }
 }
 
+// Load the operator-tunable knobs (hop limit, idle limit, maintenance
+// cadence) from an optional `--config <path>` TOML file, defaulting to
+// node-config.toml in the working directory - see config::init. Absence of
+// either the flag or the file just means "use the built-in defaults", same
+// as before this existed.
+fn load_runtime_config() {
+    let path = flag_value("--config").unwrap_or_else(|| "node-config.toml".to_string());
+    config::init(&path);
+}
+
 // Main function to start the Actix web server
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    load_runtime_config();
     // Get the configuration
     let config = get_config();
+    // --secure enables the encrypted/authenticated ECIES transport for
+    // inter-node traffic; plaintext mode (the default) keeps the current
+    // test harness working unchanged.
+    let secure_flag = args().any(|a| a == "--secure");
     let storage = Storage::new();
-    let chord: SharedChordHolder = Arc::new(RwLock::new(ChordNode::new(config.clone())));
-    let activity = ActivityTimer::new(IDLE_LIMIT); // set idle limit from config
+    let chord_node = ChordNode::new(config.clone());
+    let topology = chord_node.subscribe();
+    let reliability = Arc::clone(&chord_node.reliability);
+    let client = chord_node.client.clone();
+    let stream_client = chord_node.stream_client.clone();
+    let chord: SharedChordHolder = Arc::new(RwLock::new(chord_node));
+    let activity = ActivityTimer::new(config::idle_limit()); // set idle limit from config
     let crash_state = Arc::new(CrashState::new());
+    let secure_state = Arc::new(SecureState::new(secure_flag));
+    let drain_state = Arc::new(DrainState::new());
 
     // After creating the chord node, start the maintenance tasks
     ChordNode::maintenance(
         Arc::clone(&chord),
-        MAINTENANCE_INTERVAL_MS,
+        config::maintenance_interval_ms(),
+        Arc::clone(&crash_state),
+        get_discovery(),
+    );
+
+    // Anti-entropy repairs drift between us and our replicas in the background
+    anti_entropy::spawn(
+        Arc::clone(&chord),
+        storage.clone(),
+        config::maintenance_interval_ms(),
         Arc::clone(&crash_state),
     );
 
     let state = web::Data::new(AppState {
         storage: RwLock::new(storage),
         chord: chord,
+        topology,
+        reliability,
+        client,
+        stream_client,
         activity: activity.clone(),
         crash_state: Arc::clone(&crash_state),
+        secure: Arc::clone(&secure_state),
+        request_counters: RequestCounters::new(),
+        draining: Arc::clone(&drain_state),
     });
 
+    // Kept alive past the HttpServer factory closure (which moves its own
+    // copy of `state`) so the signal handler and idle monitor below can
+    // still drive a graceful leave through the same AppState.
+    let shutdown_state = state.clone();
+
     // Start HTTP server and obtain a server handle
-    let server = HttpServer::new(move || {
+    let http_server = HttpServer::new(move || {
         App::new()
             .app_data(state.clone())
             .wrap(CrashSimulator::new(Arc::clone(&crash_state)))
-            .wrap_fn({
-                let st = state.clone();
-                move |req, srv| {
-                    // Touch activity timer on each request
-                    st.activity.touch();
-                    let fut = srv.call(req);
-                    async move { fut.await }
-                }
-            })
+            // Enforces REQUEST_DEADLINE_MS and touches the ActivityTimer
+            // once per served request (see deadline::RequestDeadline) -
+            // replaces the ad hoc wrap_fn that used to just do the latter.
+            .wrap(RequestDeadline::new(REQUEST_DEADLINE_MS, activity.clone()))
             // All routes are present from start, but DHT operations return 503 if not initialized
             .service(api::helloworld)
             .service(api::get_storage)
             .service(api::put_storage)
+            .service(api::delete_storage)
+            .service(api::storage_batch)
             .service(api::get_node_info)
             .service(api::post_join)
             .service(api::post_leave)
@@ -109,25 +205,57 @@ async fn main() -> std::io::Result<()> {
             .service(api::ping_handler)
             .service(api::get_successor)
             .service(api::get_predecessor)
+            .service(api::get_successor_list)
             .service(api::find_successor)
             .service(api::notify)
             .service(api::set_successor)
             .service(api::set_predecessor)
             .service(api::get_known_nodes)
+            .service(api::handoff)
+            .service(api::get_replica)
+            .service(api::put_replica)
+            .service(api::merkle_root)
+            .service(api::merkle_node)
+            .service(api::node_health)
+            .service(api::metrics)
+            .service(api::get_pubkey)
+            .service(api::handshake)
     })
-    .bind((config.host.as_str(), config.port))?
-    .run();
+    // Bounds how long a graceful stop (shutdown::shutdown) waits for
+    // in-flight requests before cutting them off - see config::SHUTDOWN_GRACE_PERIOD_SECS.
+    .shutdown_timeout(SHUTDOWN_GRACE_PERIOD_SECS);
 
-    // Background idle monitor using server handle
+    // A cert/key pair (or --tls-dev) binds HTTPS via rustls instead of
+    // plaintext HTTP - see tls::server_config. NodeAddr::to_url decides
+    // independently whether peers get addressed as https://, driven by the
+    // same config (config::tls_enabled), so the two stay in sync.
+    let server = match tls::server_config() {
+        Some(tls_config) => {
+            println!("Listening on {}:{} (TLS)", config.host, config.port);
+            http_server.bind_rustls((config.host.as_str(), config.port), tls_config)?.run()
+        }
+        None => http_server.bind((config.host.as_str(), config.port))?.run(),
+    };
+
+    // Catch SIGINT/SIGTERM and leave the ring gracefully instead of letting
+    // the default disposition kill the process with our predecessor/
+    // successor left pointing at a node that's no longer there.
     let srv_handle = server.handle();
+    shutdown::spawn_signal_handlers(shutdown_state.clone(), srv_handle.clone());
+
+    // Background idle monitor using server handle - funnels through the same
+    // graceful-leave path as the signal handlers above, so an idle node
+    // departs the ring instead of just vanishing.
     actix_rt::spawn({
         let activity = activity.clone();
+        let shutdown_state = shutdown_state.clone();
+        let srv_handle = srv_handle.clone();
         async move {
             loop {
                 actix_rt::time::sleep(std::time::Duration::from_secs(60)).await;
                 if activity.is_idle() {
                     println!("No activity for 15 minutes, shutting down.");
-                    srv_handle.stop(true).await;
+                    shutdown::shutdown(&shutdown_state, &srv_handle).await;
                     break;
                 }
             }
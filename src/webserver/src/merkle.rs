@@ -0,0 +1,201 @@
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+
+use crate::config;
+
+// The identifier space is split into 2^DEPTH buckets so a single put/remove
+// only has to rehash its own bucket's leaf and the O(DEPTH) ancestors above
+// it, instead of rebuilding a summary over the whole store.
+const DEPTH: u32 = 8;
+const NUM_BUCKETS: usize = 1 << DEPTH;
+
+type Digest20 = [u8; 20];
+
+fn zero_hash() -> Digest20 {
+    [0u8; 20]
+}
+
+fn hash_pair(left: &Digest20, right: &Digest20) -> Digest20 {
+    let mut hasher = Sha1::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn entry_hash(key: &str, value: &str, version: u64, writer: u64) -> Digest20 {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(value.as_bytes());
+    hasher.update(version.to_be_bytes());
+    hasher.update(writer.to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn to_hex(bytes: &Digest20) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Which bucket a key's identifier falls into: the top DEPTH bits of its id.
+pub fn bucket_of(key_id: u64) -> usize {
+    let m = config::m();
+    let shift = if m > DEPTH { m - DEPTH } else { 0 };
+    ((key_id >> shift) as usize) & (NUM_BUCKETS - 1)
+}
+
+// A node in the tree, as returned over the wire by GET /merkle/node?path=...
+// (the path is a query parameter, not a URL segment).
+// Leaves (at the deepest level) carry `keys` instead of children so a peer
+// doing anti-entropy can fetch the actual values once it knows which keys
+// diverge.
+#[derive(Debug, Serialize)]
+pub struct MerkleNodeView {
+    pub hash: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub keys: Option<Vec<String>>,
+}
+
+// Balanced binary Merkle tree over Storage, bucketed by identifier range
+// rather than by sorted rank, so incremental updates stay cheap.
+pub struct MerkleTree {
+    // Per-bucket key -> SHA1(key || value || version || writer), used to recompute a leaf from
+    // just that bucket's contents.
+    buckets: Vec<BTreeMap<String, Digest20>>,
+    // levels[0] = leaf hashes (len NUM_BUCKETS), each following level halves
+    // in size until levels.last() holds the single root hash.
+    levels: Vec<Vec<Digest20>>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        let mut levels = Vec::new();
+        let mut size = NUM_BUCKETS;
+        loop {
+            levels.push(vec![zero_hash(); size]);
+            if size == 1 {
+                break;
+            }
+            size /= 2;
+        }
+        MerkleTree {
+            buckets: vec![BTreeMap::new(); NUM_BUCKETS],
+            levels,
+        }
+    }
+
+    fn recompute_leaf(&mut self, bucket: usize) {
+        let mut hasher = Sha1::new();
+        for hash in self.buckets[bucket].values() {
+            hasher.update(hash);
+        }
+        self.levels[0][bucket] = hasher.finalize().into();
+        self.propagate(bucket);
+    }
+
+    // Re-hash only the ancestors of `bucket`, root included.
+    fn propagate(&mut self, bucket: usize) {
+        let mut index = bucket;
+        for level in 0..self.levels.len() - 1 {
+            let (left, right) = if index % 2 == 0 { (index, index + 1) } else { (index - 1, index) };
+            let parent = hash_pair(&self.levels[level][left], &self.levels[level][right]);
+            index /= 2;
+            self.levels[level + 1][index] = parent;
+        }
+    }
+
+    pub fn put(&mut self, key: &str, value: &str, version: u64, writer: u64, key_id: u64) {
+        let bucket = bucket_of(key_id);
+        self.buckets[bucket].insert(key.to_string(), entry_hash(key, value, version, writer));
+        self.recompute_leaf(bucket);
+    }
+
+    pub fn remove(&mut self, key: &str, key_id: u64) {
+        let bucket = bucket_of(key_id);
+        if self.buckets[bucket].remove(key).is_some() {
+            self.recompute_leaf(bucket);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = MerkleTree::new();
+    }
+
+    pub fn root(&self) -> String {
+        to_hex(&self.levels.last().expect("tree always has a root level")[0])
+    }
+
+    // Resolve a path of '0'/'1' characters (root-to-node, root = "") to a
+    // node view. Returns None for an out-of-range or malformed path.
+    pub fn node(&self, path: &str) -> Option<MerkleNodeView> {
+        if path.len() >= self.levels.len() {
+            return None;
+        }
+        let level = self.levels.len() - 1 - path.len();
+        let mut index = 0usize;
+        for c in path.chars() {
+            index = (index << 1) | match c {
+                '0' => 0,
+                '1' => 1,
+                _ => return None,
+            };
+        }
+        let hash = to_hex(self.levels[level].get(index)?);
+
+        if level == 0 {
+            let keys = self.buckets[index].keys().cloned().collect();
+            Some(MerkleNodeView { hash, left: None, right: None, keys: Some(keys) })
+        } else {
+            let left = to_hex(&self.levels[level - 1][index * 2]);
+            let right = to_hex(&self.levels[level - 1][index * 2 + 1]);
+            Some(MerkleNodeView { hash, left: Some(left), right: Some(right), keys: None })
+        }
+    }
+
+    // Number of '0'/'1' characters in a path to the leaf (bucket) level.
+    pub fn leaf_depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // key_ids chosen so bucket_of places them in different buckets (DEPTH=8,
+    // so bit 8 already distinguishes bucket 0 from bucket 1) regardless of
+    // insertion order.
+    const BUCKET_0_KEY_ID: u64 = 0;
+    const BUCKET_1_KEY_ID: u64 = 1 << 8;
+
+    #[test]
+    fn test_same_inserts_converge_to_same_root() {
+        let mut a = MerkleTree::new();
+        let mut b = MerkleTree::new();
+        a.put("k1", "v1", 1, 1, BUCKET_0_KEY_ID);
+        a.put("k2", "v2", 1, 1, BUCKET_1_KEY_ID);
+        // Same keys/values, inserted in the opposite order.
+        b.put("k2", "v2", 1, 1, BUCKET_1_KEY_ID);
+        b.put("k1", "v1", 1, 1, BUCKET_0_KEY_ID);
+        assert_eq!(a.root(), b.root());
+    }
+
+    #[test]
+    fn test_changing_one_value_only_touches_its_bucket_path() {
+        let mut tree = MerkleTree::new();
+        tree.put("k1", "v1", 1, 1, BUCKET_0_KEY_ID);
+        tree.put("k2", "v2", 1, 1, BUCKET_1_KEY_ID);
+        let root_before = tree.root();
+        let bucket_1_path = "0".repeat(tree.leaf_depth() - 1) + "1";
+        let untouched_leaf_before = tree.node(&bucket_1_path).unwrap().hash;
+
+        // Only touches bucket 0 - bucket 1's leaf, and therefore everything
+        // off its path to the root, should be unaffected.
+        tree.put("k1", "v1-updated", 2, 1, BUCKET_0_KEY_ID);
+
+        let root_after = tree.root();
+        assert_ne!(root_before, root_after);
+        let untouched_leaf_after = tree.node(&bucket_1_path).unwrap().hash;
+        assert_eq!(untouched_leaf_before, untouched_leaf_after);
+    }
+}
@@ -0,0 +1,313 @@
+// Hand-rolled metrics subsystem exposed in Prometheus text format at
+// /metrics, modeled on Garage's metrics.rs. Nothing else in this crate pulls
+// in a metrics library (see reliability.rs's own RwLock<HashMap> bookkeeping),
+// so this follows the same pattern rather than adding a new dependency.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Instant;
+
+// RPC kinds issued by chord.rs's rpc_* helpers, used to key the per-kind
+// sent/failed counters below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcKind {
+    Ping,
+    Notify,
+    FindSuccessor,
+    GetPredecessor,
+    GetSuccessor,
+    GetSuccessorList,
+    SetSuccessor,
+    SetPredecessor,
+}
+
+impl RpcKind {
+    fn label(&self) -> &'static str {
+        match self {
+            RpcKind::Ping => "ping",
+            RpcKind::Notify => "notify",
+            RpcKind::FindSuccessor => "find_successor",
+            RpcKind::GetPredecessor => "get_predecessor",
+            RpcKind::GetSuccessor => "get_successor",
+            RpcKind::GetSuccessorList => "get_successor_list",
+            RpcKind::SetSuccessor => "set_successor",
+            RpcKind::SetPredecessor => "set_predecessor",
+        }
+    }
+
+    const ALL: [RpcKind; 8] = [
+        RpcKind::Ping,
+        RpcKind::Notify,
+        RpcKind::FindSuccessor,
+        RpcKind::GetPredecessor,
+        RpcKind::GetSuccessor,
+        RpcKind::GetSuccessorList,
+        RpcKind::SetSuccessor,
+        RpcKind::SetPredecessor,
+    ];
+}
+
+// Upper bound (ms) of each latency histogram bucket; Prometheus convention is
+// cumulative ("le", less-than-or-equal), with an implicit "+Inf" bucket.
+const LATENCY_BUCKETS_MS: [f64; 9] = [1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0];
+// Upper bound of each lookup hop-count bucket, same convention.
+const HOP_BUCKETS: [u64; 6] = [1, 2, 4, 8, 16, 32];
+
+struct Histogram<const N: usize> {
+    bounds: [f64; N],
+    buckets: [u64; N],
+    // Count and sum over every sample, including ones past the last bucket.
+    count: u64,
+    sum: f64,
+}
+
+impl<const N: usize> Histogram<N> {
+    fn new(bounds: [f64; N]) -> Self {
+        Histogram { bounds, buckets: [0; N], count: 0, sum: 0.0 }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, bucket) in self.bounds.iter().zip(self.buckets.iter_mut()) {
+            if value <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+
+    // Render as Prometheus `_bucket`/`_sum`/`_count` lines under `name`, with
+    // `labels` (already formatted as `key="value",...` or empty) merged in.
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        // `buckets` is already cumulative: observe() counts a sample in
+        // every bucket whose bound it fits under.
+        for (bound, bucket) in self.bounds.iter().zip(self.buckets.iter()) {
+            out.push_str(&format!("{name}_bucket{{{labels}{sep}le=\"{bound}\"}} {bucket}\n"));
+        }
+        out.push_str(&format!("{name}_bucket{{{labels}{sep}le=\"+Inf\"}} {}\n", self.count));
+        out.push_str(&format!("{name}_sum{{{labels}}} {}\n", self.sum));
+        out.push_str(&format!("{name}_count{{{labels}}} {}\n", self.count));
+    }
+}
+
+struct Counters {
+    rpc_sent: HashMap<RpcKind, u64>,
+    rpc_failed: HashMap<RpcKind, u64>,
+    rpc_latency_ms: HashMap<RpcKind, Histogram<9>>,
+    lookup_hops: Histogram<6>,
+    // Gauges: cumulative churn counts rather than current values, since "how
+    // much is the ring reshuffling" is the operationally interesting signal.
+    successor_changes: u64,
+    predecessor_changes: u64,
+    finger_refreshes: u64,
+}
+
+impl Counters {
+    fn new() -> Self {
+        Counters {
+            rpc_sent: HashMap::new(),
+            rpc_failed: HashMap::new(),
+            rpc_latency_ms: HashMap::new(),
+            lookup_hops: Histogram::new(HOP_BUCKETS.map(|b| b as f64)),
+            successor_changes: 0,
+            predecessor_changes: 0,
+            finger_refreshes: 0,
+        }
+    }
+}
+
+// Counters/histograms/gauges for routing and maintenance, shared across a
+// ChordNode's clones the same way ReliabilityTracker is.
+pub struct Metrics {
+    inner: RwLock<Counters>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics { inner: RwLock::new(Counters::new()) }
+    }
+
+    // Record one RPC attempt: `started` is when the call began, `ok` is
+    // whether it succeeded. Called from chord.rs's rpc_* helpers.
+    pub fn record_rpc(&self, kind: RpcKind, started: Instant, ok: bool) {
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let mut inner = self.inner.write().unwrap();
+        *inner.rpc_sent.entry(kind).or_insert(0) += 1;
+        if !ok {
+            *inner.rpc_failed.entry(kind).or_insert(0) += 1;
+        }
+        inner
+            .rpc_latency_ms
+            .entry(kind)
+            .or_insert_with(|| Histogram::new(LATENCY_BUCKETS_MS))
+            .observe(elapsed_ms);
+    }
+
+    // Record the hop count a find_successor lookup took to resolve, sampled
+    // at the hop that finally answers (the `hops` query parameter).
+    pub fn record_lookup_hops(&self, hops: u32) {
+        self.inner.write().unwrap().lookup_hops.observe(hops as f64);
+    }
+
+    pub fn record_successor_change(&self) {
+        self.inner.write().unwrap().successor_changes += 1;
+    }
+
+    pub fn record_predecessor_change(&self) {
+        self.inner.write().unwrap().predecessor_changes += 1;
+    }
+
+    pub fn record_finger_refresh(&self) {
+        self.inner.write().unwrap().finger_refreshes += 1;
+    }
+
+    // Render everything in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let inner = self.inner.read().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP chord_rpc_sent_total RPCs issued per kind\n");
+        out.push_str("# TYPE chord_rpc_sent_total counter\n");
+        for kind in RpcKind::ALL {
+            let n = inner.rpc_sent.get(&kind).copied().unwrap_or(0);
+            out.push_str(&format!("chord_rpc_sent_total{{kind=\"{}\"}} {n}\n", kind.label()));
+        }
+
+        out.push_str("# HELP chord_rpc_failed_total RPCs that errored or timed out, per kind\n");
+        out.push_str("# TYPE chord_rpc_failed_total counter\n");
+        for kind in RpcKind::ALL {
+            let n = inner.rpc_failed.get(&kind).copied().unwrap_or(0);
+            out.push_str(&format!("chord_rpc_failed_total{{kind=\"{}\"}} {n}\n", kind.label()));
+        }
+
+        out.push_str("# HELP chord_rpc_latency_ms RPC round-trip latency, per kind\n");
+        out.push_str("# TYPE chord_rpc_latency_ms histogram\n");
+        for kind in RpcKind::ALL {
+            if let Some(hist) = inner.rpc_latency_ms.get(&kind) {
+                hist.render(&mut out, "chord_rpc_latency_ms", &format!("kind=\"{}\"", kind.label()));
+            }
+        }
+
+        out.push_str("# HELP chord_lookup_hops Hop count find_successor took to resolve\n");
+        out.push_str("# TYPE chord_lookup_hops histogram\n");
+        inner.lookup_hops.render(&mut out, "chord_lookup_hops", "");
+
+        out.push_str("# HELP chord_successor_changes_total Times this node's successor has changed\n");
+        out.push_str("# TYPE chord_successor_changes_total counter\n");
+        out.push_str(&format!("chord_successor_changes_total {}\n", inner.successor_changes));
+
+        out.push_str("# HELP chord_predecessor_changes_total Times this node's predecessor has changed\n");
+        out.push_str("# TYPE chord_predecessor_changes_total counter\n");
+        out.push_str(&format!("chord_predecessor_changes_total {}\n", inner.predecessor_changes));
+
+        out.push_str("# HELP chord_finger_refreshes_total Finger table entries refreshed by fix_fingers\n");
+        out.push_str("# TYPE chord_finger_refreshes_total counter\n");
+        out.push_str(&format!("chord_finger_refreshes_total {}\n", inner.finger_refreshes));
+
+        out
+    }
+}
+
+// Per-request counters bumped directly from api.rs's get_storage/put_storage,
+// kept separate from `Metrics` above: these are plain monotonic counts plus
+// a small fixed-bucket histogram that every single request touches, so they
+// live in AppState as bare AtomicU64s rather than behind `Metrics`'s
+// RwLock<Counters> - no lock at all on the hot path.
+pub struct RequestCounters {
+    storage_get_local: AtomicU64,
+    storage_get_forwarded: AtomicU64,
+    storage_put_local: AtomicU64,
+    storage_put_forwarded: AtomicU64,
+    // Cumulative bucket counts over HOP_BUCKETS, same "le" convention as
+    // Histogram::render, for the X-Chord-Hop-Count value observed on each
+    // inbound /storage request.
+    hop_count_buckets: [AtomicU64; HOP_BUCKETS.len()],
+    // Every observed hop count, including ones past the last bucket (+Inf).
+    hop_count_total: AtomicU64,
+}
+
+impl RequestCounters {
+    pub fn new() -> Self {
+        RequestCounters {
+            storage_get_local: AtomicU64::new(0),
+            storage_get_forwarded: AtomicU64::new(0),
+            storage_put_local: AtomicU64::new(0),
+            storage_put_forwarded: AtomicU64::new(0),
+            hop_count_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            hop_count_total: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_storage_get(&self, forwarded: bool) {
+        let counter = if forwarded { &self.storage_get_forwarded } else { &self.storage_get_local };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_storage_put(&self, forwarded: bool) {
+        let counter = if forwarded { &self.storage_put_forwarded } else { &self.storage_put_local };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_hop_count(&self, hops: u32) {
+        for (bound, bucket) in HOP_BUCKETS.iter().zip(self.hop_count_buckets.iter()) {
+            if hops as u64 <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.hop_count_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Render these counters/gauges in Prometheus text format, alongside the
+    // current storage entry count, known-node count, and crash state -
+    // gauges that reflect state read fresh at scrape time rather than
+    // tracked incrementally.
+    pub fn render(&self, storage_entries: u64, known_nodes: u64, crashed: bool) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP chord_storage_get_total /storage GETs served, by locality\n");
+        out.push_str("# TYPE chord_storage_get_total counter\n");
+        out.push_str(&format!("chord_storage_get_total{{served=\"local\"}} {}\n", self.storage_get_local.load(Ordering::Relaxed)));
+        out.push_str(&format!("chord_storage_get_total{{served=\"forwarded\"}} {}\n", self.storage_get_forwarded.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP chord_storage_put_total /storage PUTs served, by locality\n");
+        out.push_str("# TYPE chord_storage_put_total counter\n");
+        out.push_str(&format!("chord_storage_put_total{{served=\"local\"}} {}\n", self.storage_put_local.load(Ordering::Relaxed)));
+        out.push_str(&format!("chord_storage_put_total{{served=\"forwarded\"}} {}\n", self.storage_put_forwarded.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP chord_request_hop_count X-Chord-Hop-Count observed on inbound /storage requests\n");
+        out.push_str("# TYPE chord_request_hop_count histogram\n");
+        for (bound, bucket) in HOP_BUCKETS.iter().zip(self.hop_count_buckets.iter()) {
+            out.push_str(&format!("chord_request_hop_count_bucket{{le=\"{bound}\"}} {}\n", bucket.load(Ordering::Relaxed)));
+        }
+        out.push_str(&format!("chord_request_hop_count_bucket{{le=\"+Inf\"}} {}\n", self.hop_count_total.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP chord_storage_entries Current number of keys in local storage\n");
+        out.push_str("# TYPE chord_storage_entries gauge\n");
+        out.push_str(&format!("chord_storage_entries {storage_entries}\n"));
+
+        out.push_str("# HELP chord_known_nodes Current number of nodes known to this node\n");
+        out.push_str("# TYPE chord_known_nodes gauge\n");
+        out.push_str(&format!("chord_known_nodes {known_nodes}\n"));
+
+        out.push_str("# HELP chord_crashed Whether this node is currently simulating a crash (1) or not (0)\n");
+        out.push_str("# TYPE chord_crashed gauge\n");
+        out.push_str(&format!("chord_crashed {}\n", if crashed { 1 } else { 0 }));
+
+        out
+    }
+}
+
+// Small helper so every rpc_* function in chord.rs times + records itself
+// the same way instead of repeating `Instant::now()` / `record_rpc` pairs.
+pub async fn timed_rpc<T, E>(
+    metrics: &Metrics,
+    kind: RpcKind,
+    fut: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = fut.await;
+    metrics.record_rpc(kind, started, result.is_ok());
+    result
+}
@@ -1,37 +1,218 @@
 // Network helpers
 
-use crate::chord::ChordNode;
-use crate::config::HOP_LIMIT;
+use crate::chord::{closest_preceding_on, responsible_for_on, KnownNodes, Node};
+use crate::config::hop_limit;
+use crate::error::ChordError;
+use crate::reliability::ReliabilityTracker;
+use crate::secure_state::SecureState;
+use crate::secure_transport::{self, Session};
+use crate::storage::Storage;
 use crate::utils::hash_key;
 use actix_web::HttpResponse;
-use actix_web::web::Bytes;
+use actix_web::web::{self, Bytes};
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+#[derive(serde::Deserialize)]
+struct PubkeyBody {
+    pubkey: Vec<u8>,
+}
+
+#[derive(serde::Serialize)]
+struct HandshakeBody {
+    from: String,
+    ephemeral_pubkey: Vec<u8>,
+}
+
+// Make sure we have an ECIES session with `peer`, performing the handshake
+// (fetch their static public key, then send them our ephemeral one) if we
+// don't already. The session is then reused for every later hop to this peer.
+async fn ensure_session(client: &Client, secure: &SecureState, peer_label: &str, peer_url: &str, my_label: &str) -> Option<Session> {
+    if let Some(session) = secure.session_for(peer_label) {
+        return Some(session);
+    }
+
+    let pubkey: PubkeyBody = client
+        .get(format!("{}/internal/pubkey", peer_url))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    let their_public = secp256k1::PublicKey::from_slice(&pubkey.pubkey).ok()?;
+
+    let (ephemeral_public, session) = secure_transport::initiate(&their_public);
+    secure.install_session(peer_label, session.clone());
+
+    let handshake = HandshakeBody {
+        from: my_label.to_string(),
+        ephemeral_pubkey: ephemeral_public.serialize().to_vec(),
+    };
+    client
+        .post(format!("{}/internal/handshake", peer_url))
+        .json(&handshake)
+        .send()
+        .await
+        .ok()?;
+
+    Some(session)
+}
 
-// Functions for forwarding HTTP request to next node
+// Functions for forwarding HTTP request to next node. These take a
+// `KnownNodes` snapshot rather than `&ChordNode` so callers can pull it from
+// a lock-free `watch::Receiver` (see AppState::topology) instead of holding
+// the chord RwLock for the whole forwarding round-trip.
 pub async fn forward_get(
-    chord: &ChordNode,
+    nodes: &KnownNodes,
+    reliability: &ReliabilityTracker,
+    client: &Client,
+    stream_client: &Client,
+    storage: &Storage,
     key: &str,
     hop_count: u32,
-) -> actix_web::Result<HttpResponse> {
-    if hop_count >= HOP_LIMIT {
-        return Ok(HttpResponse::BadGateway().body("Chord hop limit exceeded")); // Prevent infinite loops
+    secure: &SecureState,
+) -> Result<HttpResponse, ChordError> {
+    if hop_count >= hop_limit() {
+        return Err(ChordError::HopLimitExceeded);
     }
 
     // Hash the key to find its ID
     let key_id = hash_key(key);
-    // Check if this node is responsible for the key
-    if chord.responsible_for(key) {
-        return Ok(HttpResponse::Ok().finish()); // Placeholder: actual value retrieval not implemented here
+    // Check if this node is responsible for the key (can happen if the ring
+    // shifted between the caller's check and here), consult Storage directly
+    if responsible_for_on(nodes, key) {
+        return Ok(match storage.get(key) {
+            Some(versioned) => HttpResponse::Ok()
+                .insert_header(("X-Version", versioned.version.to_string()))
+                .body(versioned.value),
+            None => HttpResponse::NotFound().body("Key not found"),
+        });
     }
     // Find the closest preceding node
-    let next_node = chord.closest_preceding_node(key_id);
+    let next_node = closest_preceding_on(nodes, reliability, key_id);
     // Construct the URL for the next node
     let url = format!("{}/storage/{}", next_node.addr.to_url(), key);
 
-    // Forward the GET request to the next node
-    let response = chord
-        .client
-        .get(url)
+    // If --secure is on, ask for an encrypted reply and hand over our own
+    // label so the next hop can find (or start) a session with us
+    let my_label = nodes.me.addr.label();
+    let session = if secure.is_enabled() {
+        ensure_session(client, secure, &next_node.addr.label(), &next_node.addr.to_url(), &my_label).await
+    } else {
+        None
+    };
+
+    let started = std::time::Instant::now();
+
+    // An encrypted reply comes back as one opaque blob, so there's nothing
+    // to gain from streaming it - decrypt needs the whole thing in hand
+    // anyway. Keep that path exactly as it was, whole-request timeout
+    // included.
+    if let Some(session) = &session {
+        let response = client
+            .get(&url)
+            .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
+            .header("X-Secure", "1")
+            .header("X-From-Node", my_label.clone())
+            .timeout(std::time::Duration::from_millis(1000))
+            .send()
+            .await;
+
+        return match response {
+            Ok(r) => {
+                reliability.record_success(&next_node.addr.label(), started.elapsed());
+                let status = actix_web::http::StatusCode::from_u16(r.status().as_u16()).unwrap();
+                let encrypted = r.headers().contains_key("X-Encrypted");
+                let body = r
+                    .bytes()
+                    .await
+                    .unwrap_or_else(|_| Bytes::from_static(b"Error reading body"));
+
+                // Decrypt this hop's reply with the session we used to send it;
+                // whoever we hand the plaintext to next negotiates their own hop
+                if encrypted {
+                    if let Some(plaintext) = secure_transport::decrypt(session, &body) {
+                        return Ok(HttpResponse::build(status).body(plaintext));
+                    }
+                    return Err(ChordError::BadPayload("failed to decrypt forwarded response".to_string()));
+                }
+                Ok(HttpResponse::build(status).body(body))
+            }
+            Err(e) => {
+                reliability.record_failure(&next_node.addr.label());
+                Err(ChordError::ForwardFailed { target: next_node.addr.label(), source: e })
+            }
+        };
+    }
+
+    // Plaintext reply: stream it straight through instead of buffering the
+    // whole value in memory at every hop. `stream_client` only bounds
+    // connection establishment (see ChordNode::new), not the transfer
+    // itself, so a slow-but-alive node isn't killed mid-stream.
+    let response = stream_client
+        .get(&url)
+        .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
+        .send()
+        .await;
+
+    match response {
+        Ok(r) => {
+            reliability.record_success(&next_node.addr.label(), started.elapsed());
+            let status = actix_web::http::StatusCode::from_u16(r.status().as_u16()).unwrap();
+            let content_type = r
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let content_length = r
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+
+            let mut builder = HttpResponse::build(status);
+            if let Some(content_type) = content_type {
+                builder.insert_header((actix_web::http::header::CONTENT_TYPE, content_type));
+            }
+            if let Some(content_length) = content_length {
+                builder.insert_header((actix_web::http::header::CONTENT_LENGTH, content_length));
+            }
+            Ok(builder.streaming(r.bytes_stream()))
+        }
+        Err(e) => {
+            reliability.record_failure(&next_node.addr.label());
+            Err(ChordError::ForwardFailed { target: next_node.addr.label(), source: e })
+        }
+    }
+}
+
+// Forwards a DELETE toward the key's owner. Unlike forward_get/forward_put
+// there's no value body to protect, so this doesn't participate in the
+// --secure session handshake.
+pub async fn forward_delete(
+    nodes: &KnownNodes,
+    reliability: &ReliabilityTracker,
+    client: &Client,
+    key: &str,
+    hop_count: u32,
+) -> Result<HttpResponse, ChordError> {
+    if hop_count >= hop_limit() {
+        return Err(ChordError::HopLimitExceeded);
+    }
+
+    let key_id = hash_key(key);
+    if responsible_for_on(nodes, key) {
+        return Err(ChordError::Misrouted("delete: already responsible for key".to_string()));
+    }
+    let next_node = closest_preceding_on(nodes, reliability, key_id);
+    let url = format!("{}/storage/{}", next_node.addr.to_url(), key);
+
+    let started = std::time::Instant::now();
+    let response = client
+        .delete(url)
         .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
         .timeout(std::time::Duration::from_millis(1000))
         .send()
@@ -39,54 +220,252 @@ pub async fn forward_get(
 
     match response {
         Ok(r) => {
+            reliability.record_success(&next_node.addr.label(), started.elapsed());
             let status = actix_web::http::StatusCode::from_u16(r.status().as_u16()).unwrap();
-            let body = r
-                .bytes()
-                .await
-                .unwrap_or_else(|_| Bytes::from_static(b"Error reading body"));
+            let body = r.bytes().await.unwrap_or_else(|_| Bytes::from_static(b""));
             Ok(HttpResponse::build(status).body(body))
         }
-        Err(e) => Ok(HttpResponse::BadGateway().body(format!("forward error: {}", e))),
+        Err(e) => {
+            reliability.record_failure(&next_node.addr.label());
+            Err(ChordError::ForwardFailed { target: next_node.addr.label(), source: e })
+        }
     }
 }
 
 pub async fn forward_put(
-    chord: &ChordNode,
+    nodes: &KnownNodes,
+    reliability: &ReliabilityTracker,
+    client: &Client,
     key: &str,
     value: Bytes,
     hop_count: u32,
-) -> actix_web::Result<HttpResponse> {
-    if hop_count >= HOP_LIMIT {
-        return Ok(HttpResponse::BadGateway().body("Chord hop limit exceeded")); // Prevent infinite loops
+    secure: &SecureState,
+) -> Result<HttpResponse, ChordError> {
+    if hop_count >= hop_limit() {
+        return Err(ChordError::HopLimitExceeded);
     }
 
     // Hash the key to find its ID
     let key_id = hash_key(key);
-    // Check if this node is responsible for the key
-    if chord.responsible_for(key) {
-        return Ok(HttpResponse::Ok().finish()); // Placeholder: actual value storage not implemented here
+    // Callers are expected to have already checked responsible_for_on and
+    // taken the local-storage path themselves (see api.rs::put_storage,
+    // which also replicates to the successor list - something this function
+    // has no way to do, since it's only handed a plain Storage, not the
+    // client/KnownNodes replicate_write needs). Storing directly here used
+    // to silently skip that replication for anyone who forwarded anyway;
+    // erroring instead turns a future misroute into a loud bug report
+    // instead of a landmine.
+    if responsible_for_on(nodes, key) {
+        return Err(ChordError::Misrouted("forward_put: already responsible for key".to_string()));
     }
     // Find the closest preceding node
-    let next_node = chord.closest_preceding_node(key_id);
+    let next_node = closest_preceding_on(nodes, reliability, key_id);
     // Construct the URL for the next node
     let url = format!("{}/storage/{}", next_node.addr.to_url(), key);
 
+    // If --secure is on, encrypt the value for this hop and hand over our own
+    // label so the next hop can find (or start) a session with us
+    let my_label = nodes.me.addr.label();
+    let session = if secure.is_enabled() {
+        ensure_session(client, secure, &next_node.addr.label(), &next_node.addr.to_url(), &my_label).await
+    } else {
+        None
+    };
+    let outgoing_body = match &session {
+        Some(session) => Bytes::from(secure_transport::encrypt(session, &value)),
+        None => value.clone(),
+    };
+
     // Forward the PUT request to the next node
-    let response = chord
-        .client
+    let started = std::time::Instant::now();
+    let mut request = client
         .put(url)
         .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
-        .timeout(std::time::Duration::from_millis(1000))
-        .body(value.clone())
+        .timeout(std::time::Duration::from_millis(1000));
+    if session.is_some() {
+        request = request
+            .header("X-Encrypted", "1")
+            .header("X-From-Node", my_label.clone());
+    }
+    let response = request.body(outgoing_body).send().await;
+
+    match response {
+        Ok(r) => {
+            reliability.record_success(&next_node.addr.label(), started.elapsed());
+            let status = actix_web::http::StatusCode::from_u16(r.status().as_u16()).unwrap();
+            let body = r.bytes().await.unwrap_or_else(|_| Bytes::from_static(b""));
+            Ok(HttpResponse::build(status).body(body))
+        }
+        Err(e) => {
+            reliability.record_failure(&next_node.addr.label());
+            Err(ChordError::ForwardFailed { target: next_node.addr.label(), source: e })
+        }
+    }
+}
+
+// Forwards a plaintext PUT toward the key's owner by streaming the incoming
+// request payload straight into the outgoing one via
+// reqwest::Body::wrap_stream, instead of buffering the whole value on this
+// hop the way forward_put does. Only reachable for unencrypted PUTs this
+// node isn't responsible for, with --secure off - an encrypted hop needs the
+// whole body to decrypt, a --secure hop needs it to encrypt, and a value
+// we're responsible for needs the whole body to validate and store, so those
+// still go through forward_put.
+pub async fn forward_put_stream(
+    nodes: &KnownNodes,
+    reliability: &ReliabilityTracker,
+    stream_client: &Client,
+    key: &str,
+    payload: web::Payload,
+    hop_count: u32,
+) -> Result<HttpResponse, ChordError> {
+    if hop_count >= hop_limit() {
+        return Err(ChordError::HopLimitExceeded);
+    }
+
+    let key_id = hash_key(key);
+    let next_node = closest_preceding_on(nodes, reliability, key_id);
+    let url = format!("{}/storage/{}", next_node.addr.to_url(), key);
+
+    let body_stream = payload.map(|chunk| chunk.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)));
+
+    // `stream_client` only bounds connection establishment (see
+    // ChordNode::new), so the transfer itself isn't killed partway through
+    // just because a large value takes a while to arrive.
+    let started = std::time::Instant::now();
+    let response = stream_client
+        .put(url)
+        .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
+        .body(reqwest::Body::wrap_stream(body_stream))
         .send()
         .await;
 
     match response {
         Ok(r) => {
+            reliability.record_success(&next_node.addr.label(), started.elapsed());
             let status = actix_web::http::StatusCode::from_u16(r.status().as_u16()).unwrap();
             let body = r.bytes().await.unwrap_or_else(|_| Bytes::from_static(b""));
             Ok(HttpResponse::build(status).body(body))
         }
-        Err(e) => Ok(HttpResponse::BadGateway().body(format!("forward error: {}", e))),
+        Err(e) => {
+            reliability.record_failure(&next_node.addr.label());
+            Err(ChordError::ForwardFailed { target: next_node.addr.label(), source: e })
+        }
     }
-}
\ No newline at end of file
+}
+// A single operation in a POST /storage-batch request.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BatchOp {
+    Get { key: String },
+    Put { key: String, value: String },
+}
+
+impl BatchOp {
+    pub fn key(&self) -> &str {
+        match self {
+            BatchOp::Get { key } => key,
+            BatchOp::Put { key, .. } => key,
+        }
+    }
+}
+
+// One operation's outcome in a /storage-batch response, reported
+// individually so a crashed node failing one sub-request doesn't fail the
+// whole batch.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct BatchResult {
+    pub key: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+fn batch_error_results(ops: Vec<(usize, BatchOp)>, status: u16, message: &str) -> Vec<(usize, BatchResult)> {
+    ops.into_iter()
+        .map(|(i, op)| {
+            (
+                i,
+                BatchResult {
+                    key: op.key().to_string(),
+                    status,
+                    value: None,
+                    version: None,
+                    error: Some(message.to_string()),
+                },
+            )
+        })
+        .collect()
+}
+
+// Forwards a batch of ops that this node isn't responsible for. Rather than
+// walking the ring one key at a time the way forward_get/forward_put do,
+// every op is hashed and grouped by the node closest_preceding_on would
+// route it to, then one /storage-batch sub-request per group is issued
+// concurrently via futures::future::join_all - so the cost of a batch is one
+// round-trip per distinct next hop, not one per key.
+pub async fn forward_batch(
+    nodes: &KnownNodes,
+    reliability: &ReliabilityTracker,
+    client: &Client,
+    ops: Vec<(usize, BatchOp)>,
+    hop_count: u32,
+) -> Vec<(usize, BatchResult)> {
+    if ops.is_empty() {
+        return Vec::new();
+    }
+    if hop_count >= hop_limit() {
+        return batch_error_results(ops, 502, "Chord hop limit exceeded");
+    }
+
+    // Group by next-hop target, keeping each op's original index so results
+    // can be handed back in request order once every group's reply is in.
+    let mut groups: HashMap<String, (Node, Vec<(usize, BatchOp)>)> = HashMap::new();
+    for (i, op) in ops {
+        let target = closest_preceding_on(nodes, reliability, hash_key(op.key()));
+        groups.entry(target.addr.label()).or_insert_with(|| (target, Vec::new())).1.push((i, op));
+    }
+
+    let requests = groups.into_values().map(|(target, group)| {
+        let client = client.clone();
+        async move {
+            let url = format!("{}/storage-batch", target.addr.to_url());
+            let request_ops: Vec<&BatchOp> = group.iter().map(|(_, op)| op).collect();
+            let started = std::time::Instant::now();
+            let response = client
+                .post(url)
+                .header("X-Chord-Hop-Count", (hop_count + 1).to_string())
+                .json(&request_ops)
+                .timeout(std::time::Duration::from_millis(1000))
+                .send()
+                .await;
+
+            match response {
+                Ok(r) if r.status().is_success() => {
+                    reliability.record_success(&target.addr.label(), started.elapsed());
+                    let expected = group.len();
+                    match r.json::<Vec<BatchResult>>().await {
+                        Ok(results) if results.len() == expected => {
+                            group.into_iter().map(|(i, _)| i).zip(results).collect::<Vec<_>>()
+                        }
+                        _ => batch_error_results(group, 502, "Malformed batch response"),
+                    }
+                }
+                Ok(r) => {
+                    reliability.record_success(&target.addr.label(), started.elapsed());
+                    batch_error_results(group, r.status().as_u16(), "Forwarded batch request failed")
+                }
+                Err(e) => {
+                    reliability.record_failure(&target.addr.label());
+                    batch_error_results(group, 502, &format!("forward error: {}", e))
+                }
+            }
+        }
+    });
+
+    futures::future::join_all(requests).await.into_iter().flatten().collect()
+}
@@ -0,0 +1,106 @@
+// Per-peer reliability bookkeeping used by routing to prefer healthy, fast
+// nodes over ones that are currently timing out or erroring.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+const FAILURE_THRESHOLD: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const EWMA_ALPHA: f64 = 0.2;
+
+struct Entry {
+    consecutive_failures: u32,
+    rtt_ewma_ms: f64,
+    // Set once failures cross FAILURE_THRESHOLD; cleared on the next success.
+    dead_since: Option<Instant>,
+}
+
+impl Entry {
+    fn new() -> Self {
+        Entry { consecutive_failures: 0, rtt_ewma_ms: 0.0, dead_since: None }
+    }
+
+    // Exponential backoff before we'll try a dead node again, capped at MAX_BACKOFF.
+    fn is_dead(&self) -> bool {
+        match self.dead_since {
+            Some(since) => {
+                let extra = self.consecutive_failures.saturating_sub(FAILURE_THRESHOLD).min(5);
+                let backoff = (BASE_BACKOFF * 2u32.pow(extra)).min(MAX_BACKOFF);
+                since.elapsed() < backoff
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeHealthView {
+    pub consecutive_failures: u32,
+    pub rtt_ewma_ms: f64,
+    pub alive: bool,
+}
+
+// Tracks recent success/failure counts and a latency EWMA per node label
+// (host:port), shared across a ChordNode's clones.
+pub struct ReliabilityTracker {
+    entries: RwLock<HashMap<String, Entry>>,
+}
+
+impl ReliabilityTracker {
+    pub fn new() -> Self {
+        ReliabilityTracker { entries: RwLock::new(HashMap::new()) }
+    }
+
+    pub fn record_success(&self, label: &str, rtt: Duration) {
+        let mut entries = self.entries.write().unwrap();
+        let entry = entries.entry(label.to_string()).or_insert_with(Entry::new);
+        entry.consecutive_failures = 0;
+        entry.dead_since = None;
+        let sample_ms = rtt.as_secs_f64() * 1000.0;
+        entry.rtt_ewma_ms = if entry.rtt_ewma_ms == 0.0 {
+            sample_ms
+        } else {
+            EWMA_ALPHA * sample_ms + (1.0 - EWMA_ALPHA) * entry.rtt_ewma_ms
+        };
+    }
+
+    pub fn record_failure(&self, label: &str) {
+        let mut entries = self.entries.write().unwrap();
+        let entry = entries.entry(label.to_string()).or_insert_with(Entry::new);
+        entry.consecutive_failures += 1;
+        if entry.consecutive_failures >= FAILURE_THRESHOLD && entry.dead_since.is_none() {
+            entry.dead_since = Some(Instant::now());
+        }
+    }
+
+    // A node with no history yet is assumed alive so newly discovered peers
+    // aren't routed around before we've ever tried them.
+    pub fn is_dead(&self, label: &str) -> bool {
+        self.entries.read().unwrap().get(label).map(|e| e.is_dead()).unwrap_or(false)
+    }
+
+    pub fn rtt_ewma_ms(&self, label: &str) -> f64 {
+        self.entries.read().unwrap().get(label).map(|e| e.rtt_ewma_ms).unwrap_or(0.0)
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, NodeHealthView> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(label, entry)| {
+                (
+                    label.clone(),
+                    NodeHealthView {
+                        consecutive_failures: entry.consecutive_failures,
+                        rtt_ewma_ms: entry.rtt_ewma_ms,
+                        alive: !entry.is_dead(),
+                    },
+                )
+            })
+            .collect()
+    }
+}
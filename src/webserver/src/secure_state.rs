@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::secure_transport::{NodeIdentity, Session};
+
+/// Shared state backing the --secure transport: this node's identity keypair
+/// and the established sessions, one per peer label, reused bidirectionally
+/// for every hop-by-hop encrypted message to or from that peer.
+pub struct SecureState {
+    enabled: bool,
+    identity: NodeIdentity,
+    sessions: RwLock<HashMap<String, Session>>,
+}
+
+impl SecureState {
+    pub fn new(enabled: bool) -> Self {
+        SecureState {
+            enabled,
+            identity: NodeIdentity::generate(),
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn identity(&self) -> &NodeIdentity {
+        &self.identity
+    }
+
+    /// Look up an already-established session for a peer, if a handshake has
+    /// happened with them before (in either direction).
+    pub fn session_for(&self, peer_label: &str) -> Option<Session> {
+        self.sessions.read().unwrap().get(peer_label).cloned()
+    }
+
+    pub fn install_session(&self, peer_label: &str, session: Session) {
+        self.sessions.write().unwrap().insert(peer_label.to_string(), session);
+    }
+}
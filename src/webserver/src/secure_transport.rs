@@ -0,0 +1,174 @@
+// Optional encrypted/authenticated transport for inter-node traffic, enabled
+// with --secure. Modeled on the ECIES + AES session scheme used for peer wire
+// encryption in Ethereum-style P2P stacks: an ephemeral ECDH exchange derives
+// a symmetric session via HKDF, then each message is AES-CTR encrypted and
+// HMAC-SHA256 tagged. Encryption is hop-by-hop - each link in a forwarded
+// Chord request is encrypted independently under that link's own session.
+
+use aes::Aes256;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use sha2::Sha256;
+
+use crate::utils::hash_bytes;
+
+type Aes256Ctr = ctr::Ctr64BE<Aes256>;
+type HmacSha256 = Hmac<Sha256>;
+
+const IV_LEN: usize = 16;
+const TAG_LEN: usize = 32;
+
+// This node's long-lived identity keypair, used to authenticate handshakes.
+pub struct NodeIdentity {
+    pub secret: SecretKey,
+    pub public: PublicKey,
+}
+
+impl NodeIdentity {
+    pub fn generate() -> Self {
+        let secp = Secp256k1::new();
+        let secret = SecretKey::new(&mut OsRng);
+        let public = PublicKey::from_secret_key(&secp, &secret);
+        NodeIdentity { secret, public }
+    }
+}
+
+// Derive a node id from a public key the same way hash_key derives one from
+// an address label. Not yet wired into the ring's id assignment - switching
+// the default id scheme is a bigger change than --secure should make on its
+// own - but available for a future secure-mode node identity.
+pub fn node_id_from_pubkey(public: &PublicKey) -> u64 {
+    hash_bytes(&public.serialize())
+}
+
+// Symmetric keys for one peer session, derived once per handshake and reused
+// for every subsequent encrypted message in either direction with that peer.
+#[derive(Clone)]
+pub struct Session {
+    aes_key: [u8; 32],
+    hmac_key: [u8; 32],
+}
+
+fn derive_session(shared_secret: &[u8]) -> Session {
+    let hk = Hkdf::<Sha256>::new(None, shared_secret);
+    let mut okm = [0u8; 64];
+    hk.expand(b"inf3200-secure-transport", &mut okm)
+        .expect("64 bytes is a valid HKDF-SHA256 output length");
+    let mut aes_key = [0u8; 32];
+    let mut hmac_key = [0u8; 32];
+    aes_key.copy_from_slice(&okm[..32]);
+    hmac_key.copy_from_slice(&okm[32..]);
+    Session { aes_key, hmac_key }
+}
+
+// Initiator side: generate an ephemeral keypair, ECDH it against the peer's
+// static public key, and derive the session. The ephemeral public key must be
+// sent to the peer (see the /internal/handshake RPC) so it can derive the
+// identical session via `respond`.
+pub fn initiate(their_public: &PublicKey) -> (PublicKey, Session) {
+    let secp = Secp256k1::new();
+    let ephemeral_secret = SecretKey::new(&mut OsRng);
+    let ephemeral_public = PublicKey::from_secret_key(&secp, &ephemeral_secret);
+    let shared = SharedSecret::new(their_public, &ephemeral_secret);
+    (ephemeral_public, derive_session(&shared.secret_bytes()))
+}
+
+// Responder side: given the initiator's ephemeral public key and our own
+// static secret, derive the same session without generating anything new.
+pub fn respond(ephemeral_public: &PublicKey, my_secret: &SecretKey) -> Session {
+    let shared = SharedSecret::new(ephemeral_public, my_secret);
+    derive_session(&shared.secret_bytes())
+}
+
+// Encrypt `plaintext` under `session`, returning iv || ciphertext || hmac_tag.
+pub fn encrypt(session: &Session, plaintext: &[u8]) -> Vec<u8> {
+    let mut iv = [0u8; IV_LEN];
+    OsRng.fill_bytes(&mut iv);
+
+    let mut buffer = plaintext.to_vec();
+    let mut cipher = Aes256Ctr::new(&session.aes_key.into(), &iv.into());
+    cipher.apply_keystream(&mut buffer);
+
+    let mut mac = HmacSha256::new_from_slice(&session.hmac_key).expect("HMAC accepts any key length");
+    mac.update(&iv);
+    mac.update(&buffer);
+    let tag = mac.finalize().into_bytes();
+
+    let mut out = Vec::with_capacity(IV_LEN + buffer.len() + TAG_LEN);
+    out.extend_from_slice(&iv);
+    out.extend_from_slice(&buffer);
+    out.extend_from_slice(&tag);
+    out
+}
+
+// Verify the HMAC tag and decrypt. Returns None (rather than panicking) on a
+// malformed payload or a tag mismatch, so callers reject forged/corrupt
+// traffic instead of acting on it.
+pub fn decrypt(session: &Session, payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() < IV_LEN + TAG_LEN {
+        return None;
+    }
+    let (iv, rest) = payload.split_at(IV_LEN);
+    let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+    let mut mac = HmacSha256::new_from_slice(&session.hmac_key).expect("HMAC accepts any key length");
+    mac.update(iv);
+    mac.update(ciphertext);
+    mac.verify_slice(tag).ok()?;
+
+    let mut buffer = ciphertext.to_vec();
+    let mut cipher = Aes256Ctr::new(&session.aes_key.into(), iv.into());
+    cipher.apply_keystream(&mut buffer);
+    Some(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        // Both sides of a handshake derive the same session from opposite
+        // ends of the same ECDH exchange - encrypting under one and
+        // decrypting under the other is the scheme's whole point.
+        let responder = NodeIdentity::generate();
+        let (ephemeral_public, initiator_session) = initiate(&responder.public);
+        let responder_session = respond(&ephemeral_public, &responder.secret);
+
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let ciphertext = encrypt(&initiator_session, plaintext);
+        let decrypted = decrypt(&responder_session, &ciphertext).expect("valid payload should decrypt");
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_tag() {
+        let responder = NodeIdentity::generate();
+        let (ephemeral_public, initiator_session) = initiate(&responder.public);
+        let responder_session = respond(&ephemeral_public, &responder.secret);
+
+        let mut ciphertext = encrypt(&initiator_session, b"tamper with me");
+        // Flip a bit in the last byte, which falls inside the HMAC tag.
+        let last = ciphertext.len() - 1;
+        ciphertext[last] ^= 0xff;
+
+        assert!(decrypt(&responder_session, &ciphertext).is_none());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_truncated_payload() {
+        let responder = NodeIdentity::generate();
+        let (ephemeral_public, initiator_session) = initiate(&responder.public);
+        let responder_session = respond(&ephemeral_public, &responder.secret);
+
+        let ciphertext = encrypt(&initiator_session, b"short");
+        let truncated = &ciphertext[..IV_LEN + TAG_LEN - 1];
+
+        assert!(decrypt(&responder_session, truncated).is_none());
+    }
+}
@@ -0,0 +1,41 @@
+use actix_web::{dev::ServerHandle, web};
+use tokio::signal::unix::{signal, SignalKind};
+
+use crate::api::graceful_leave;
+use crate::AppState;
+
+// Installs SIGINT/SIGTERM handlers and ties them into the same graceful
+// departure the idle monitor in main.rs triggers, so however a node's
+// process ends - Ctrl-C, `kill`, an orchestrator's SIGTERM, or just sitting
+// idle - it leaves the ring the way POST /leave would instead of abandoning
+// its predecessor/successor with dangling pointers and its keys unhanded.
+// Spawned once at startup; runs for the lifetime of the process.
+pub fn spawn_signal_handlers(state: web::Data<AppState>, srv_handle: ServerHandle) {
+    actix_rt::spawn(async move {
+        // SIGINT/SIGTERM handlers must be installed before the first await,
+        // or a signal delivered in the meantime would just terminate the
+        // process the old way (default disposition) instead of being caught.
+        let mut sigint = signal(SignalKind::interrupt()).expect("failed to install SIGINT handler");
+        let mut sigterm = signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+
+        tokio::select! {
+            _ = sigint.recv() => println!("Received SIGINT, leaving the ring before shutting down."),
+            _ = sigterm.recv() => println!("Received SIGTERM, leaving the ring before shutting down."),
+        }
+
+        shutdown(&state, &srv_handle).await;
+    });
+}
+
+// Perform a graceful ring departure, then stop the server with a bounded
+// grace period (config::SHUTDOWN_GRACE_PERIOD_SECS, set via
+// HttpServer::shutdown_timeout at startup) so requests already in flight get
+// to finish instead of being cut off mid-response. Shared by the signal
+// handlers above and main.rs's idle monitor, so every shutdown trigger takes
+// the same path out of the ring.
+pub async fn shutdown(state: &web::Data<AppState>, srv_handle: &ServerHandle) {
+    if let Err(e) = graceful_leave(state).await {
+        eprintln!("Error leaving DHT during shutdown: {}", e);
+    }
+    srv_handle.stop(true).await;
+}
@@ -1,11 +1,37 @@
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use crate::merkle::{MerkleNodeView, MerkleTree};
+use crate::utils::hash_key;
+
+// A stored value tagged with a Lamport-style version counter and the id of
+// the node that wrote it. `(version, writer)` totally orders concurrent
+// writes so replicas converge on the same value regardless of arrival order.
+//
+// Deletes are tombstones rather than removals (`deleted: true`, value
+// cleared): they carry a version/writer pair like any other write, so a
+// concurrent put and delete converge via the same (version, writer) compare
+// instead of a delete racing a replica push and the key coming back.
+// `#[serde(default)]` keeps old replicas/handoffs that never sent the field
+// parseable as non-deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedValue {
+    pub value: String,
+    pub version: u64,
+    pub writer: u64,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
 // A thread-safe storage handler using RwLock for concurrent read/write access.
 // Allows multiple readers or one writer at a time.
 #[derive(Clone)]
 pub struct Storage {
-    storage: Arc<RwLock<HashMap<String, String>>>,
+    storage: Arc<RwLock<HashMap<String, VersionedValue>>>,
+    // Bucketed Merkle summary kept in lock-step with `storage`, used for
+    // anti-entropy sync against replicas (see merkle.rs).
+    merkle: Arc<RwLock<MerkleTree>>,
 }
 
 impl Storage {
@@ -13,22 +39,58 @@ impl Storage {
     pub fn new() -> Self {
         Storage {
             storage: Arc::new(RwLock::new(HashMap::new())),
+            merkle: Arc::new(RwLock::new(MerkleTree::new())),
         }
     }
 
     // Get a value by key
-    pub fn get(&self, key: &str) -> Option<String> {
+    pub fn get(&self, key: &str) -> Option<VersionedValue> {
         // Acquire a read lock to safely access the storage
         let storage = self.storage.read().unwrap();
         // Clone the value to return it
         storage.get(key).cloned()
     }
 
-    // Put a key-value pair into the storage
-    pub fn put(&self, key: String, value: String) {
-        // Acquire a write lock to safely modify the storage
+    // Locally originated write: this node is the writer, so its value always
+    // wins over whatever is currently stored - bump the version past it.
+    // Returns the version assigned, so callers can echo it back to clients
+    // for compare-and-set and propagate it to replicas.
+    pub fn put(&self, key: String, value: String, writer: u64) -> u64 {
+        let mut storage = self.storage.write().unwrap();
+        let version = storage.get(&key).map(|current| current.version).unwrap_or(0) + 1;
+        self.merkle.write().unwrap().put(&key, &value, version, writer, hash_key(&key));
+        storage.insert(key, VersionedValue { value, version, writer, deleted: false });
+        version
+    }
+
+    // Locally originated delete: writes a tombstone rather than removing the
+    // entry outright, so the same (version, writer) ordering used by `merge`
+    // resolves a delete racing a concurrent replica push deterministically
+    // instead of the old value winning just because it arrived later.
+    // Returns the version assigned, same as `put`.
+    pub fn delete(&self, key: String, writer: u64) -> u64 {
         let mut storage = self.storage.write().unwrap();
-        storage.insert(key, value);
+        let version = storage.get(&key).map(|current| current.version).unwrap_or(0) + 1;
+        self.merkle.write().unwrap().put(&key, "", version, writer, hash_key(&key));
+        storage.insert(key, VersionedValue { value: String::new(), version, writer, deleted: true });
+        version
+    }
+
+    // Conditional merge used for replica pushes and anti-entropy pulls: only
+    // overwrites when the incoming (version, writer) pair is strictly greater
+    // than what we hold, so concurrent writes converge deterministically.
+    // Returns whether the incoming value was applied.
+    pub fn merge(&self, key: String, incoming: VersionedValue) -> bool {
+        let mut storage = self.storage.write().unwrap();
+        let should_apply = match storage.get(&key) {
+            Some(current) => (incoming.version, incoming.writer) > (current.version, current.writer),
+            None => true,
+        };
+        if should_apply {
+            self.merkle.write().unwrap().put(&key, &incoming.value, incoming.version, incoming.writer, hash_key(&key));
+            storage.insert(key, incoming);
+        }
+        should_apply
     }
 
     // Clear all key-value pairs from storage
@@ -36,6 +98,45 @@ impl Storage {
         // Acquire a write lock to safely modify the storage
         let mut storage = self.storage.write().unwrap();
         storage.clear();
+        self.merkle.write().unwrap().clear();
+    }
+
+    // Remove a single key, if present
+    pub fn remove(&self, key: &str) {
+        let mut storage = self.storage.write().unwrap();
+        if storage.remove(key).is_some() {
+            self.merkle.write().unwrap().remove(key, hash_key(key));
+        }
+    }
+
+    // Snapshot all key-value pairs currently held (used for key handoff on ring changes)
+    pub fn entries(&self) -> Vec<(String, VersionedValue)> {
+        let storage = self.storage.read().unwrap();
+        storage.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    // Number of keys currently held, tombstones included - cheaper than
+    // entries().len() since it doesn't clone every value.
+    pub fn len(&self) -> usize {
+        self.storage.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Root hash of the Merkle summary, for a cheap "are we in sync" check against a peer
+    pub fn merkle_root(&self) -> String {
+        self.merkle.read().unwrap().root()
+    }
+
+    // Resolve a root-to-node path (see merkle::MerkleTree::node) for anti-entropy descent
+    pub fn merkle_node(&self, path: &str) -> Option<MerkleNodeView> {
+        self.merkle.read().unwrap().node(path)
+    }
+
+    pub fn merkle_leaf_depth(&self) -> usize {
+        self.merkle.read().unwrap().leaf_depth()
     }
 }
 
@@ -46,14 +147,14 @@ mod tests {
     #[test]
     fn test_storage_put_get() {
         let storage = Storage::new();
-        storage.put("key1".to_string(), "value1".to_string());
+        storage.put("key1".to_string(), "value1".to_string(), 1);
         let value = storage.get("key1");
-        assert_eq!(value, Some("value1".to_string()));
+        assert_eq!(value.map(|v| v.value), Some("value1".to_string()));
     }
     #[test]
     fn test_storage_get_nonexistent() {
         let storage = Storage::new();
         let value = storage.get("nonexistent");
-        assert_eq!(value, None);
+        assert!(value.is_none());
     }
 }
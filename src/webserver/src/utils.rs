@@ -1,20 +1,63 @@
-use sha1::{Digest, Sha1};
-use crate::config::M;
+use sha1::{Digest as _, Sha1};
+use sha2::{Digest as _, Sha256};
 
-// Function to hash a key using SHA-1 and return a u64 identifier
+use crate::config::{self, HashAlgorithm};
+
+// Function to hash a key using the configured hash algorithm and return a
+// u64 identifier, masked to the configured identifier width.
 pub fn hash_key(key: &str) -> u64 {
-    let mut hasher = Sha1::new();
-    hasher.update(key.as_bytes());
-    let result = hasher.finalize();
-    // Use the first M / 8 bytes of the hash as the identifier
-    let n = M as usize / 8;
-    let mut id_bytes = [0u8; 8];
-    id_bytes[8 - n..].copy_from_slice(&result[..n]);
-    u64::from_be_bytes(id_bytes)
+    hash_bytes(key.as_bytes())
+}
+
+// Same identifier scheme as hash_key, but over raw bytes rather than a
+// string - used to derive a node id from things other than an address label
+// (e.g. a public key, for the secure transport).
+pub fn hash_bytes(data: &[u8]) -> u64 {
+    mask_to_width(digest_leading_u64(data), config::m())
+}
+
+// Hash `data` with the configured algorithm (see config::HashAlgorithm) and
+// read its first 8 bytes, big-endian, as a u64. Every supported digest is at
+// least 8 bytes, so this never has to pad.
+fn digest_leading_u64(data: &[u8]) -> u64 {
+    let mut leading = [0u8; 8];
+    match config::hash_algorithm() {
+        HashAlgorithm::Sha1 => {
+            let mut hasher = Sha1::new();
+            hasher.update(data);
+            leading.copy_from_slice(&hasher.finalize()[..8]);
+        }
+        HashAlgorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            hasher.update(data);
+            leading.copy_from_slice(&hasher.finalize()[..8]);
+        }
+        HashAlgorithm::Blake3 => {
+            leading.copy_from_slice(&blake3::hash(data).as_bytes()[..8]);
+        }
+    }
+    u64::from_be_bytes(leading)
 }
 
-// Check if id is in the (start, end) interval on the identifier circle
-pub fn in_interval_open_open(id: u64, start: u64, end: u64) -> bool {
+// Keep only the top `m` bits of a 64-bit, big-endian-read hash, so every
+// identifier - regardless of which algorithm produced it - lands in the same
+// [0, 2^m) range the rest of the ring's interval math assumes. `m == 64` is
+// the full-circle edge case the tests below note: every bit is already
+// "top", so there's nothing to shift out.
+fn mask_to_width(raw: u64, m: u32) -> u64 {
+    if m >= 64 {
+        raw
+    } else {
+        raw >> (64 - m)
+    }
+}
+
+// Check if id is in the (start, end) interval on the identifier circle.
+// `m` is the ring width all three values are assumed to already be masked
+// to (see mask_to_width) - asserted here rather than re-masked, since a
+// value that doesn't fit means a caller upstream forgot to hash/mask it.
+pub fn in_interval_open_open(id: u64, start: u64, end: u64, m: u32) -> bool {
+    debug_assert!(m >= 64 || (id | start | end) >> m == 0, "id/start/end wider than the configured M bits");
     if start < end {
         id > start && id < end
     } else if start > end {
@@ -24,8 +67,9 @@ pub fn in_interval_open_open(id: u64, start: u64, end: u64) -> bool {
     }
 }
 
-// Check if id is in the (start, end] interval on the identifier circle
-pub fn in_interval_open_closed(id: u64, start: u64, end: u64) -> bool {
+// Check if id is in the (start, end] interval on the identifier circle.
+pub fn in_interval_open_closed(id: u64, start: u64, end: u64, m: u32) -> bool {
+    debug_assert!(m >= 64 || (id | start | end) >> m == 0, "id/start/end wider than the configured M bits");
     if start < end {
         id > start && id <= end
     } else if start > end {
@@ -45,25 +89,26 @@ mod tests {
         // Test that hash_key produces a u64 within the identifier space
         let key = "example_key";
         let id = hash_key(key);
-        if M < 64 {
-            assert!(id < (1u64 << M));
+        let m = config::m();
+        if m < 64 {
+            assert!(id < (1u64 << m));
         }
         // When M == 64, any u64 value is valid
     }
     #[test]
     fn test_in_interval_open_open() {
-        assert!(in_interval_open_open(5, 3, 7)); // 5 is between 3 and 7
-        assert!(!in_interval_open_open(3, 3, 7)); // 3 is not in (3,7)
-        assert!(!in_interval_open_open(7, 3, 7)); // 7 is not in (3,7)
-        assert!(in_interval_open_open(1, 7, 3)); // Wrap around case
-        assert!(!in_interval_open_open(7, 7, 3)); // 7 is not in (7,3)
+        assert!(in_interval_open_open(5, 3, 7, 16)); // 5 is between 3 and 7
+        assert!(!in_interval_open_open(3, 3, 7, 16)); // 3 is not in (3,7)
+        assert!(!in_interval_open_open(7, 3, 7, 16)); // 7 is not in (3,7)
+        assert!(in_interval_open_open(1, 7, 3, 16)); // Wrap around case
+        assert!(!in_interval_open_open(7, 7, 3, 16)); // 7 is not in (7,3)
     }
     #[test]
     fn test_in_interval_open_closed() {
-        assert!(in_interval_open_closed(5, 3, 7)); // 5 is between 3 and 7
-        assert!(!in_interval_open_closed(3, 3, 7)); // 3 is not in (3,7]
-        assert!(in_interval_open_closed(7, 3, 7)); // 7 is in (3,7]
-        assert!(in_interval_open_closed(1, 7, 3)); // Wrap around case
-        assert!(!in_interval_open_closed(7, 7, 3)); // 7 is not in (7,3]
+        assert!(in_interval_open_closed(5, 3, 7, 16)); // 5 is between 3 and 7
+        assert!(!in_interval_open_closed(3, 3, 7, 16)); // 3 is not in (3,7]
+        assert!(in_interval_open_closed(7, 3, 7, 16)); // 7 is in (3,7]
+        assert!(in_interval_open_closed(1, 7, 3, 16)); // Wrap around case
+        assert!(!in_interval_open_closed(7, 7, 3, 16)); // 7 is not in (7,3]
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+// A small worker framework for ChordNode's periodic maintenance jobs,
+// mirroring Garage's background/worker.rs: a `Worker` trait with a declared
+// schedule (initial delay + interval) and an async work() step, plus a
+// scheduler that spawns one task per worker. Every worker runs in its own
+// loop, so only one instance of a given worker's work() is ever in flight at
+// a time, and CrashState-skipping plus per-worker timeouts are applied
+// uniformly instead of being smeared across copy-pasted tokio::spawn
+// closures (see chord.rs's stabilize/fix_fingers/check_predecessor/discovery
+// workers).
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::simulate::CrashState;
+
+#[async_trait]
+pub trait Worker: Send + Sync {
+    // For diagnostics only; not currently surfaced anywhere.
+    fn name(&self) -> &'static str;
+
+    // Delay before this worker's first tick, so workers with the same
+    // interval don't all fire on the same tick (replaces the hand-jittered
+    // offsets maintenance used to compute itself).
+    fn initial_delay(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn interval(&self) -> Duration;
+
+    // Wrapped around every work() call; a hung RPC chain can't stall this
+    // worker's schedule forever.
+    fn timeout(&self) -> Duration;
+
+    async fn work(&self);
+}
+
+// Spawn one detached task per worker. Each task is a single sequential loop,
+// so only one call to that worker's work() is ever in flight at a time.
+pub fn spawn_all(workers: Vec<Box<dyn Worker>>, crash_state: Arc<CrashState>) {
+    for worker in workers {
+        let crash_state = Arc::clone(&crash_state);
+        tokio::spawn(async move {
+            tokio::time::sleep(worker.initial_delay()).await;
+
+            let mut interval = tokio::time::interval(worker.interval());
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if crash_state.is_crashed() {
+                    continue;
+                }
+                let _ = tokio::time::timeout(worker.timeout(), worker.work()).await;
+            }
+        });
+    }
+}